@@ -0,0 +1,66 @@
+//! Part 2 with the single pass scanner against the first edition rewriting each line, on a
+//! generated input.
+//!
+//! `cargo bench -p aoc-2023-day-1 --bench scanner`
+
+use aoc_2023_day_1::scanner::Scanner;
+use aoc_2023_day_1::vocabulary::Vocabulary;
+use aoc_2023_day_1::SOLUTION;
+use aoc_core::random::Rng;
+use aoc_core::{Options, Part};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: u64 = 16 << 20;
+
+fn main() {
+    let mut input = Vec::new();
+    (SOLUTION.streaming.as_ref().unwrap().generate)(&mut Rng::new(2023), SIZE, &mut input);
+    let input = String::from_utf8(input).unwrap();
+
+    let Part::Implemented(scanner) = SOLUTION.parts[1] else {
+        unreachable!()
+    };
+    let replace = SOLUTION.variants.iter().find(|variant| variant.name == "replace").unwrap().solve;
+
+    let options = Options::default();
+    assert_eq!(scanner(&input, &options), replace(&input, &options), "both versions must agree");
+
+    report("part 2, scanner", input.len(), time(|| scanner(&input, &options)));
+    report("part 2, replace", input.len(), time(|| replace(&input, &options)));
+
+    let scanner = Scanner::new(&Vocabulary::spelled_digits());
+    report(
+        "first and last digits only",
+        input.len(),
+        time(|| {
+            input
+                .lines()
+                .map(|line| (scanner.first(line.as_bytes()).unwrap() * 10 + scanner.last(line.as_bytes()).unwrap()) as u64)
+                .sum::<u64>()
+        }),
+    );
+}
+
+fn report(name: &str, bytes: usize, time: Duration) {
+    let mib = bytes as f64 / (1 << 20) as f64;
+    println!(
+        "{:<32} {:>6.1} MiB in {:>8.3} ms => {:>8.1} MiB/s",
+        name,
+        mib,
+        time.as_secs_f64() * 1000.0,
+        mib / time.as_secs_f64()
+    );
+}
+
+/// Best time out of enough runs to last about a second.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_secs(1) {
+        let run_start = Instant::now();
+        black_box(f());
+        best = best.min(run_start.elapsed());
+    }
+    best
+}
@@ -0,0 +1,109 @@
+//! Which digits of a line make its value, set with `--param extract=<extraction>`.
+//!
+//! The puzzle's calibration value is made of the first and the last digit, `ends:1`. `ends:<k>`
+//! takes the first and last `k` digits, `all` every digit in order, and `positions:<list>` the
+//! digits at the given positions, counted from the first digit from 0 or from the last one from -1.
+//! A value past `u64::MAX` makes the line invalid:
+//!
+//! ```
+//! use aoc_2023_day_1::extraction::Extraction;
+//! use aoc_2023_day_1::scanner::Scanner;
+//! use aoc_2023_day_1::vocabulary::Vocabulary;
+//!
+//! let scanner = Scanner::new(&Vocabulary::spelled_digits());
+//! let line = b"a1b2sevenineight";
+//! assert_eq!("ends:1".parse::<Extraction>().unwrap().value(&scanner, line), Some(Ok(18)));
+//! assert_eq!("ends:2".parse::<Extraction>().unwrap().value(&scanner, line), Some(Ok(1298)));
+//! assert_eq!("all".parse::<Extraction>().unwrap().value(&scanner, line), Some(Ok(12798)));
+//! assert_eq!("positions:1,-2".parse::<Extraction>().unwrap().value(&scanner, line), Some(Ok(29)));
+//! assert_eq!("ends:6".parse::<Extraction>().unwrap().value(&scanner, line), None);
+//!
+//! assert_eq!(Extraction::number(&[1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1, 5]), Ok(u64::MAX));
+//! let error = Extraction::number(&[1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1, 6]).unwrap_err();
+//! assert_eq!(error.to_string(), "expected a value of at most 18446744073709551615, found `18446744073709551616`");
+//! ```
+
+use crate::scanner::Scanner;
+use aoc_core::parse::ParseError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extraction {
+    /// The first `k` digits, then the last `k` digits.
+    Ends(usize),
+    /// Every digit, in order.
+    All,
+    /// The digits at these positions: from the first digit from 0, from the last one from -1.
+    Positions(Vec<isize>),
+}
+
+impl Default for Extraction {
+    fn default() -> Extraction {
+        Extraction::Ends(1)
+    }
+}
+
+impl Extraction {
+    /// Digits making the value of a line, `None` if the line does not have enough of them.
+    pub fn digits(&self, scanner: &Scanner, line: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            // The only digits needed are found without listing all of them
+            Extraction::Ends(1) => Some(vec![scanner.first(line)?, scanner.last(line)?]),
+            Extraction::Ends(k) => {
+                let (first, mut last) = (scanner.digits(line), scanner.digits_from_end(line));
+                if first.len() < *k || last.len() < *k {
+                    return None;
+                }
+                last.truncate(*k);
+                Some(first[..*k].iter().copied().chain(last.into_iter().rev()).collect())
+            }
+            Extraction::All => Some(scanner.digits(line)).filter(|digits| !digits.is_empty()),
+            Extraction::Positions(positions) => {
+                let (first, last) = (scanner.digits(line), scanner.digits_from_end(line));
+                positions
+                    .iter()
+                    .map(|&position| match usize::try_from(position) {
+                        Ok(index) => first.get(index).copied(),
+                        Err(_) => last.get(position.unsigned_abs() - 1).copied(),
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Value of a line: the number its extracted digits write, `None` if the line does not have
+    /// enough digits.
+    pub fn value(&self, scanner: &Scanner, line: &[u8]) -> Option<Result<u64, ParseError>> {
+        Some(Extraction::number(&self.digits(scanner, line)?))
+    }
+
+    /// Number written by decimal digits, an error if it does not fit.
+    pub fn number(digits: &[u8]) -> Result<u64, ParseError> {
+        let value = digits.iter().try_fold(0_u64, |value, &digit| value.checked_mul(10)?.checked_add(digit as u64));
+        value.ok_or_else(|| {
+            let written = digits.iter().map(|&digit| (b'0' + digit) as char).collect::<String>();
+            ParseError::invalid("a value of at most 18446744073709551615", &written)
+        })
+    }
+}
+
+impl FromStr for Extraction {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Extraction, String> {
+        let invalid = || format!("invalid extraction {:?}, expected ends:<k>, all or positions:<list>", text);
+        match text.split_once(':') {
+            None if text == "all" => Ok(Extraction::All),
+            Some(("ends", k)) => match k.parse() {
+                Ok(k) if k > 0 => Ok(Extraction::Ends(k)),
+                _ => Err(invalid()),
+            },
+            Some(("positions", list)) => list
+                .split(',')
+                .map(|position| position.trim().parse().map_err(|_| invalid()))
+                .collect::<Result<_, _>>()
+                .map(Extraction::Positions),
+            _ => Err(invalid()),
+        }
+    }
+}
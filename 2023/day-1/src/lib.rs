@@ -0,0 +1,273 @@
+pub mod extraction;
+pub mod scanner;
+pub mod vocabulary;
+
+use aoc_core::arith::{self, Number};
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::parse::ParseError;
+use aoc_core::random::Rng;
+use aoc_core::{Failure, Options, ParamError};
+use extraction::Extraction;
+use scanner::Scanner;
+use std::io::{BufRead, Write};
+use vocabulary::Vocabulary;
+
+/// Digits picked on a single line, kept so that `--explain` can show how the sum was built.
+struct Calibration {
+    digits: Vec<u8>,
+    /// An error if the digits write too large a value.
+    value: Result<u64, ParseError>,
+}
+
+/// What to do with the lines without enough digits, set with `--param missing_digits=<policy>`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum MissingDigits {
+    /// The part fails, naming the first such line.
+    Error,
+    /// The lines are left out of the sum.
+    Skip,
+    /// The lines count as a calibration value of zero.
+    Zero,
+}
+
+impl MissingDigits {
+    fn from_options(options: &Options) -> Result<MissingDigits, ParamError> {
+        match options.param("missing_digits") {
+            None | Some("error") => Ok(MissingDigits::Error),
+            Some("skip") => Ok(MissingDigits::Skip),
+            Some("zero") => Ok(MissingDigits::Zero),
+            Some(policy) => Err(ParamError::new(
+                "missing_digits",
+                format!("invalid policy {:?}, expected error, skip or zero", policy),
+            )),
+        }
+    }
+}
+
+/// Number of offending line numbers listed by a summary.
+const MISSING_DIGITS_SHOWN: usize = 20;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 1,
+    parse: parse_input,
+    part_1: solve_part_1::<u64>,
+    part_2: solve_part_2::<u64>,
+    stream: stream_input,
+    generate: generate_input,
+    variants: [
+        part_2 "replace": solve_part_2_replace::<u64>,
+    ],
+}
+
+fn parse_input(input: &str) -> Vec<String> {
+    input.lines().map(|line| line.to_string()).collect()
+}
+
+fn solve_part_1<T: Number + TryFrom<u64>>(lines: &[String], options: &Options) -> Result<T, Failure> {
+    let scanner = Scanner::new(&Vocabulary::digits());
+    let extraction = extraction(options)?;
+    let calibrations = lines.iter().map(|line| extract_calibration_value(&scanner, &extraction, line)).collect::<Vec<_>>();
+
+    sum_calibrations("part 1", lines, &calibrations, options)
+}
+
+fn solve_part_2<T: Number + TryFrom<u64>>(lines: &[String], options: &Options) -> Result<T, Failure> {
+    let scanner = Scanner::new(&part_2_vocabulary(options)?);
+    let extraction = extraction(options)?;
+    let calibrations = lines.iter().map(|line| extract_calibration_value(&scanner, &extraction, line)).collect::<Vec<_>>();
+
+    sum_calibrations("part 2", lines, &calibrations, options)
+}
+
+/// Sums the calibration values, dealing with the lines without enough digits as the options ask.
+/// A value too large fails the part, naming the first such line.
+fn sum_calibrations<T: Number + TryFrom<u64>>(
+    part: &str,
+    lines: &[String],
+    calibrations: &[Option<Calibration>],
+    options: &Options,
+) -> Result<T, Failure> {
+    let policy = MissingDigits::from_options(options)?;
+    if options.explain {
+        explain_calibrations(part, lines, calibrations, policy);
+    }
+
+    let missing = calibrations.iter().enumerate().filter(|(_, calibration)| calibration.is_none());
+    let missing = missing.map(|(index, _)| index + 1).collect::<Vec<_>>();
+    if !missing.is_empty() {
+        report_missing_digits(part, missing.len(), &missing);
+        if policy == MissingDigits::Error {
+            return Err(ParseError::missing("digits").at_line(missing[0]).into());
+        }
+    }
+
+    let values = calibrations.iter().enumerate().filter_map(|(index, calibration)| Some((index, calibration.as_ref()?)));
+    let values = values.map(|(index, calibration)| calibration.value.clone().map_err(|error| error.at_line(index + 1)));
+    Ok(arith::sum(values.collect::<Result<Vec<_>, _>>()?.into_iter().map(arith::convert)))
+}
+
+/// Prints on stderr how many lines do not have enough digits, and the first of them.
+fn report_missing_digits(part: &str, count: usize, first_lines: &[usize]) {
+    let shown = first_lines.iter().take(MISSING_DIGITS_SHOWN).map(|line| line.to_string()).collect::<Vec<_>>();
+    eprintln!(
+        "{}: {} line(s) without enough digits: {}{}",
+        part,
+        count,
+        shown.join(", "),
+        if count > shown.len() { ", ..." } else { "" }
+    );
+}
+
+/// Digits making the value of a line, given by the `extract` parameter, the puzzle's ones by default.
+fn extraction(options: &Options) -> Result<Extraction, ParamError> {
+    match options.param("extract") {
+        Some(extraction) => extraction.parse().map_err(|error| ParamError::new("extract", error)),
+        None => Ok(Extraction::default()),
+    }
+}
+
+/// Vocabulary given by the `vocabulary` and `ignore_case` parameters, the puzzle's one by default.
+fn part_2_vocabulary(options: &Options) -> Result<Vocabulary, ParamError> {
+    let vocabulary = match options.param("vocabulary") {
+        Some(list) => Vocabulary::load(list).map_err(|error| ParamError::new("vocabulary", error))?,
+        None => Vocabulary::spelled_digits(),
+    };
+    Ok(vocabulary.with_ignore_case(options.param("ignore_case") == Some("true")))
+}
+
+/// First edition of part 2: rewrites each line with its spelled digits replaced, then looks for
+/// digits only. Knows the English words only, whatever the vocabulary parameters.
+fn solve_part_2_replace<T: Number + TryFrom<u64>>(lines: &[String], options: &Options) -> Result<T, Failure> {
+    let scanner = Scanner::new(&Vocabulary::digits());
+    let extraction = extraction(options)?;
+    let calibrations = lines
+        .iter()
+        .map(|line| extract_calibration_value(&scanner, &extraction, &replace_spelled_digits(line)))
+        .collect::<Vec<_>>();
+
+    sum_calibrations("part 2", lines, &calibrations, options)
+}
+
+/// Keeps only the digits of a line, spelled ones included.
+fn replace_spelled_digits(line: &str) -> String {
+    let spelled_digits = Vocabulary::built_in("english").unwrap();
+    let mut line_replaced = String::new();
+    for i in 0..line.len() {
+        let c = line.chars().nth(i).unwrap();
+        if c.is_numeric() {
+            line_replaced.push(c);
+        }
+        else {
+            for (p, r) in spelled_digits.words() {
+                if &line[i.. line.len().min(i + p.len())] == p {
+                    line_replaced.push((b'0' + r) as char);
+                }
+            }
+        }
+    }
+    line_replaced
+}
+
+/// Calibration value of a line, `None` if it does not have enough digits.
+fn extract_calibration_value(scanner: &Scanner, extraction: &Extraction, line: &str) -> Option<Calibration> {
+    let digits = extraction.digits(scanner, line.as_bytes())?;
+    let value = Extraction::number(&digits);
+
+    Some(Calibration { digits, value })
+}
+
+fn explain_calibrations(part: &str, lines: &[String], calibrations: &[Option<Calibration>], policy: MissingDigits) {
+    println!("{}:", part);
+    for (line_number, (line, calibration)) in lines.iter().zip(calibrations).enumerate() {
+        match (calibration, policy) {
+            (Some(calibration), _) => println!(
+                "  line {}: {:?} -> digits {} => {}",
+                line_number + 1,
+                line,
+                calibration.digits.iter().map(|digit| digit.to_string()).collect::<Vec<_>>().join(" "),
+                match &calibration.value {
+                    Ok(value) => value.to_string(),
+                    Err(_) => "too large".to_string(),
+                }
+            ),
+            (None, MissingDigits::Error) => println!("  line {}: {:?} -> not enough digits", line_number + 1, line),
+            (None, MissingDigits::Skip) => println!("  line {}: {:?} -> not enough digits, skipped", line_number + 1, line),
+            (None, MissingDigits::Zero) => println!("  line {}: {:?} -> not enough digits => 0", line_number + 1, line),
+        }
+    }
+}
+
+/// Sums both parts line by line, without keeping the document in memory. An invalid vocabulary
+/// fails part 2 only, which is then left out of the pass.
+fn stream_input(reader: &mut dyn BufRead, options: &Options) -> [String; 2] {
+    let (extraction, policy) = match (extraction(options), MissingDigits::from_options(options)) {
+        (Ok(extraction), Ok(policy)) => (extraction, policy),
+        (Err(error), _) | (_, Err(error)) => return [1, 2].map(|part| Failure::from(error.clone()).report(part)),
+    };
+    let mut sums = [0_u64, 0_u64];
+    let vocabulary = part_2_vocabulary(options);
+    let scanners = [Some(Scanner::new(&Vocabulary::digits())), vocabulary.as_ref().ok().map(Scanner::new)];
+    // Count and first line numbers of the lines without enough digits, for each part
+    let mut missing = [(0, Vec::new()), (0, Vec::new())];
+    // First line of a value too large, for each part
+    let mut too_large: [Option<ParseError>; 2] = [None, None];
+    let mut line_number = 0;
+
+    for_each_line(reader, |line| {
+        line_number += 1;
+        let parts = sums.iter_mut().zip(&scanners).zip(missing.iter_mut().zip(too_large.iter_mut()));
+        for ((sum, scanner), ((count, first_lines), too_large)) in parts {
+            let Some(scanner) = scanner else {
+                continue;
+            };
+            match extraction.value(scanner, line.as_bytes()) {
+                Some(Ok(value)) => *sum = arith::add(*sum, value),
+                Some(Err(error)) => {
+                    too_large.get_or_insert_with(|| error.at_line(line_number));
+                }
+                None => {
+                    *count += 1;
+                    if first_lines.len() < MISSING_DIGITS_SHOWN {
+                        first_lines.push(line_number);
+                    }
+                }
+            }
+        }
+    });
+
+    let mut answers = sums.map(|sum| sum.to_string());
+    for (part_index, ((count, first_lines), too_large)) in missing.iter().zip(too_large).enumerate() {
+        // As when loading, the lines without enough digits are reported first
+        let mut error = too_large;
+        if *count > 0 {
+            report_missing_digits(&format!("part {}", part_index + 1), *count, first_lines);
+            if policy == MissingDigits::Error {
+                error = Some(ParseError::missing("digits").at_line(first_lines[0]));
+            }
+        }
+        if let Some(error) = error {
+            answers[part_index] = Failure::from(error).report(part_index + 1);
+        }
+    }
+    if let Err(error) = vocabulary {
+        answers[1] = Failure::from(error).report(2);
+    }
+    answers
+}
+
+/// Lines of letters mixed with digits and spelled digits, with at least one actual digit.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let spelled_digits = Vocabulary::built_in("english").unwrap();
+    generate_lines(rng, size, out, |rng, line| {
+        let digit_position = rng.below(8);
+        for i in 0..8 {
+            match rng.below(4) {
+                _ if i == digit_position => line.push(b'1' + rng.below(9) as u8),
+                0 => line.extend_from_slice(rng.pick(spelled_digits.words()).0.as_bytes()),
+                1 => line.push(b'1' + rng.below(9) as u8),
+                _ => line.push(b'a' + rng.below(26) as u8),
+            }
+        }
+    });
+}
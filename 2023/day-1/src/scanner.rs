@@ -0,0 +1,216 @@
+//! Finds the first and last digit of a line in a single pass, spelled ones included.
+//!
+//! Every word is matched at once by an Aho–Corasick automaton, run forwards from the start of the
+//! line for the first digit and over the reversed words from the end for the last one, so that
+//! overlapping words such as `eightwo` count for both their digits.
+//!
+//! The digits of a line are the longest word starting at each position, from either end: the last
+//! digit is the longest word starting last, not the word ending last, which only differ when a
+//! word contains another one.
+//!
+//! ```
+//! use aoc_2023_day_1::scanner::Scanner;
+//! use aoc_2023_day_1::vocabulary::Vocabulary;
+//!
+//! let scanner = Scanner::new(&Vocabulary::spelled_digits());
+//! assert_eq!(scanner.first(b"xtwone3four"), Some(2));
+//! assert_eq!(scanner.last(b"xtwone3four"), Some(4));
+//! assert_eq!((scanner.first(b"eightwo"), scanner.last(b"eightwo")), (Some(8), Some(2)));
+//! assert_eq!(Scanner::new(&Vocabulary::digits()).first(b"eightwo"), None);
+//!
+//! // The longest of the words starting at a position wins, from either end
+//! let roman = Scanner::new(&Vocabulary::load("roman").unwrap().with_ignore_case(true));
+//! assert_eq!((roman.first(b"xiv"), roman.last(b"xiv")), (Some(4), Some(5)));
+//! assert_eq!(roman.digits(b"xViiix"), [8, 3, 2, 9]);
+//! assert_eq!(roman.digits_from_end(b"xViiix"), [9, 2, 3, 8]);
+//! assert_eq!((roman.first(b"xViiix"), roman.last(b"xViiix")), (Some(8), Some(9)));
+//! assert_eq!(roman.last(b"viii"), Some(1));
+//! ```
+
+use crate::vocabulary::Vocabulary;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// Automata finding the first and the last word of a vocabulary in a line.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Scanner {
+        let words = vocabulary.words().iter().map(|(word, digit)| {
+            let word = if vocabulary.ignore_case() { word.to_ascii_lowercase() } else { word.clone() };
+            (word.into_bytes(), *digit)
+        });
+        let words = words.collect::<Vec<_>>();
+        let reversed = words.iter().map(|(word, digit)| (word.iter().rev().copied().collect(), *digit));
+
+        Scanner {
+            forward: Automaton::new(words.iter().cloned(), vocabulary.ignore_case()),
+            backward: Automaton::new(reversed, vocabulary.ignore_case()),
+        }
+    }
+
+    /// Digits of the line in order, from the longest word starting at each position. Overlapping
+    /// words all count, so `eightwo` gives 8 then 2.
+    pub fn digits(&self, line: &[u8]) -> Vec<u8> {
+        self.forward.words(line.iter().copied())
+    }
+
+    /// Digits of the line from its end: the same ones as [`Scanner::digits`], in reverse order.
+    pub fn digits_from_end(&self, line: &[u8]) -> Vec<u8> {
+        let mut digits = self.digits(line);
+        digits.reverse();
+        digits
+    }
+
+    /// Digit of the word starting first in the line, the longest one if several do.
+    pub fn first(&self, line: &[u8]) -> Option<u8> {
+        self.forward.earliest(line.iter().copied())
+    }
+
+    /// Digit of the word starting last in the line, the longest one if several do.
+    pub fn last(&self, line: &[u8]) -> Option<u8> {
+        // Reversed words starting last end first in the reversed line
+        self.backward.earliest_end(line.iter().rev().copied())
+    }
+}
+
+const NONE: u32 = u32::MAX;
+
+/// Aho–Corasick automaton, with its failure links folded into a full transition table.
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[u32; 256]>,
+    /// Value and length of the word spelled by each state, if it is one.
+    words: Vec<Option<(u8, usize)>>,
+    /// Value and length of the longest word ending in each state.
+    outputs: Vec<Option<(u8, usize)>>,
+    /// Next state along the failure links which is a word, `NONE` if there is none.
+    dictionary: Vec<u32>,
+    longest: usize,
+}
+
+impl Automaton {
+    /// Automaton for lowercase `words` when ignoring case.
+    fn new(words: impl Iterator<Item = (Vec<u8>, u8)>, ignore_case: bool) -> Automaton {
+        let mut automaton = Automaton {
+            transitions: vec![[NONE; 256]],
+            words: vec![None],
+            outputs: Vec::new(),
+            dictionary: Vec::new(),
+            longest: 0,
+        };
+
+        // Trie of the words
+        for (word, value) in words {
+            let mut state = 0;
+            for &byte in &word {
+                if automaton.transitions[state][byte as usize] == NONE {
+                    automaton.transitions[state][byte as usize] = automaton.transitions.len() as u32;
+                    automaton.transitions.push([NONE; 256]);
+                    automaton.words.push(None);
+                }
+                state = automaton.transitions[state][byte as usize] as usize;
+            }
+            automaton.words[state] = Some((value, word.len()));
+            automaton.longest = automaton.longest.max(word.len());
+        }
+
+        // Breadth first, so that the failure state of a state is complete before it is used
+        let mut failures = vec![0; automaton.transitions.len()];
+        automaton.outputs = automaton.words.clone();
+        automaton.dictionary = vec![NONE; automaton.transitions.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let next = automaton.transitions[state][byte];
+                let fallback = if state == 0 { 0 } else { automaton.transitions[failures[state]][byte] };
+                if next == NONE {
+                    automaton.transitions[state][byte] = fallback;
+                } else {
+                    let next = next as usize;
+                    let failure = if state == 0 { 0 } else { fallback as usize };
+                    failures[next] = failure;
+                    automaton.outputs[next] = automaton.outputs[next].or(automaton.outputs[failure]);
+                    automaton.dictionary[next] =
+                        if automaton.words[failure].is_some() { failure as u32 } else { automaton.dictionary[failure] };
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if ignore_case {
+            for transitions in automaton.transitions.iter_mut() {
+                for byte in b'A'..=b'Z' {
+                    transitions[byte as usize] = transitions[byte.to_ascii_lowercase() as usize];
+                }
+            }
+        }
+
+        automaton
+    }
+
+    /// Value of the word starting first in the text, the longest one if several do.
+    fn earliest(&self, text: impl Iterator<Item = u8>) -> Option<u8> {
+        let mut state = 0;
+        let mut best: Option<(usize, usize, u8)> = None;
+
+        for (i, byte) in text.enumerate() {
+            // A word ending from here on would start after the best one
+            if best.is_some_and(|(start, _, _)| i >= start + self.longest) {
+                break;
+            }
+            state = self.transitions[state][byte as usize] as usize;
+            if let Some((value, length)) = self.outputs[state] {
+                let start = i + 1 - length;
+                if best.is_none_or(|(best_start, best_length, _)| start < best_start || (start == best_start && length > best_length)) {
+                    best = Some((start, length, value));
+                }
+            }
+        }
+
+        best.map(|(_, _, value)| value)
+    }
+
+    /// Value of the word ending first in the text, the longest one if several do.
+    fn earliest_end(&self, text: impl Iterator<Item = u8>) -> Option<u8> {
+        let mut state = 0;
+        for byte in text {
+            state = self.transitions[state][byte as usize] as usize;
+            if let Some((value, _)) = self.outputs[state] {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Every word of the text, overlapping ones included, as `(start, length, value)` ordered by
+    /// end, then from the longest.
+    fn matches(&self, text: impl Iterator<Item = u8>) -> Vec<(usize, usize, u8)> {
+        let mut state = 0;
+        let mut matches = Vec::new();
+
+        for (i, byte) in text.enumerate() {
+            state = self.transitions[state][byte as usize] as usize;
+            let mut word = if self.words[state].is_some() { state as u32 } else { self.dictionary[state] };
+            while word != NONE {
+                let (value, length) = self.words[word as usize].unwrap();
+                matches.push((i + 1 - length, length, value));
+                word = self.dictionary[word as usize];
+            }
+        }
+
+        matches
+    }
+
+    /// Values of the longest word starting at each position of the text, in order.
+    fn words(&self, text: impl Iterator<Item = u8>) -> Vec<u8> {
+        let mut matches = self.matches(text);
+        matches.sort_by_key(|&(start, length, _)| (start, Reverse(length)));
+        matches.dedup_by_key(|&mut (start, _, _)| start);
+        matches.into_iter().map(|(_, _, value)| value).collect()
+    }
+}
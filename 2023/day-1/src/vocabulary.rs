@@ -0,0 +1,137 @@
+//! Words the scanner recognises as digits, built in or loaded from a file.
+//!
+//! A vocabulary is given to the day as `--param vocabulary=<list>`, a comma separated list of
+//! built in vocabularies (`digits`, `english`, `french`, `german`, `spanish`, `zero`, `roman`) and
+//! of files, and `--param ignore_case` makes the matching ignore the case of ASCII letters.
+//! Files have one word and its digit per line, blank lines and `#` comments being ignored:
+//!
+//! ```
+//! use aoc_2023_day_1::vocabulary::Vocabulary;
+//!
+//! let vocabulary = Vocabulary::parse("# Dutch\neen 1\ntwee 2\ndrie 3").unwrap();
+//! assert_eq!(vocabulary.words()[1], ("twee".to_string(), 2));
+//!
+//! let error = Vocabulary::parse("een 1\ntwee two").unwrap_err();
+//! assert_eq!(error.to_string(), "line 2: expected a digit, found `two`");
+//! ```
+
+use aoc_core::parse::{self, ParseError, Pattern};
+use std::fs::read_to_string;
+
+/// Words standing for digits, and whether they match regardless of case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+    ignore_case: bool,
+}
+
+const BUILT_IN: [(&str, [&str; 10]); 7] = [
+    ("digits", ["", "1", "2", "3", "4", "5", "6", "7", "8", "9"]),
+    ("english", ["", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"]),
+    ("french", ["", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"]),
+    ("german", ["", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"]),
+    ("spanish", ["", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"]),
+    ("zero", ["0 zero", "", "", "", "", "", "", "", "", ""]),
+    ("roman", ["", "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"]),
+];
+
+const WORD: Pattern = Pattern::new("{} {}");
+
+impl Vocabulary {
+    /// Vocabulary of the puzzle's first part: the digits `1` to `9`.
+    pub fn digits() -> Vocabulary {
+        Vocabulary::built_in("digits").unwrap()
+    }
+
+    /// Vocabulary of the puzzle's second part: the digits `1` to `9`, written or spelled.
+    pub fn spelled_digits() -> Vocabulary {
+        Vocabulary::load("digits,english").unwrap()
+    }
+
+    /// One of the built in vocabularies, by name.
+    pub fn built_in(name: &str) -> Option<Vocabulary> {
+        let (_, words) = BUILT_IN.iter().find(|(built_in, _)| *built_in == name)?;
+        let words = words
+            .iter()
+            .enumerate()
+            .flat_map(|(digit, words)| words.split_whitespace().map(move |word| (word.to_string(), digit as u8)))
+            .collect();
+        Some(Vocabulary {
+            words,
+            ignore_case: false,
+        })
+    }
+
+    /// Union of a comma separated list of built in vocabularies and vocabulary files.
+    pub fn load(list: &str) -> Result<Vocabulary, String> {
+        let mut vocabulary = Vocabulary::default();
+        for item in list.split(',').map(str::trim) {
+            let other = match Vocabulary::built_in(item) {
+                Some(built_in) => built_in,
+                None => {
+                    let text = read_to_string(item).map_err(|error| format!("{}: {}", item, error))?;
+                    Vocabulary::parse(&text).map_err(|error| format!("{}: {}", item, error))?
+                }
+            };
+            vocabulary.extend(other).map_err(|word| format!("{}: `{}` stands for two digits", item, word))?;
+        }
+        Ok(vocabulary)
+    }
+
+    /// Vocabulary of a configuration file, one word and its digit per line.
+    pub fn parse(text: &str) -> Result<Vocabulary, ParseError> {
+        let mut vocabulary = Vocabulary::default();
+        let lines = parse::lines(text, |line| {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                return Ok(None);
+            }
+
+            let (word, digit): (&str, &str) = WORD.parse(line)?;
+            match digit.trim().parse::<u8>() {
+                Ok(digit) if digit <= 9 => Ok(Some((word.trim().to_string(), digit))),
+                _ => Err(ParseError::invalid("a digit", digit)),
+            }
+        })?;
+
+        for (line_number, word) in lines.into_iter().enumerate() {
+            if let Some(word) = word {
+                vocabulary
+                    .add(word.0, word.1)
+                    .map_err(|word| ParseError::invalid("a word standing for a single digit", &word).at_line(line_number + 1))?;
+            }
+        }
+        Ok(vocabulary)
+    }
+
+    /// Adds a word, failing with the word if it already stands for another digit.
+    pub fn add(&mut self, word: String, digit: u8) -> Result<(), String> {
+        match self.words.iter().find(|(known, _)| *known == word) {
+            Some(&(_, known)) if known != digit => Err(word),
+            Some(_) => Ok(()),
+            None => {
+                self.words.push((word, digit));
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds the words of another vocabulary, failing with the first one standing for another digit.
+    pub fn extend(&mut self, other: Vocabulary) -> Result<(), String> {
+        other.words.into_iter().try_for_each(|(word, digit)| self.add(word, digit))
+    }
+
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Vocabulary {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn words(&self) -> &[(String, u8)] {
+        &self.words
+    }
+
+    /// Whether words match regardless of the case of their ASCII letters.
+    pub fn ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+}
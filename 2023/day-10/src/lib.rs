@@ -1,5 +1,12 @@
-use std::io::stdin;
-use std::time::Instant;
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 10,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Tile {
@@ -32,7 +39,7 @@ impl Tile {
         &self,
         y_current: isize,
         x_current: isize,
-        tiles: &Vec<Vec<Tile>>,
+        tiles: &[Vec<Tile>],
     ) -> Option<((isize, isize), (isize, isize))> {
         match self {
             Tile::None | Tile::Erased => None,
@@ -73,31 +80,27 @@ impl Tile {
                     }
                 }
 
-                Some((
-                    (coords[0].0 as isize, coords[0].1 as isize),
-                    (coords[1].0 as isize, coords[1].1 as isize),
-                ))
+                Some((coords[0], coords[1]))
             }
         }
     }
 }
 
-fn parse_input() -> Vec<Vec<Tile>> {
-    stdin()
+fn parse_input(input: &str) -> Vec<Vec<Tile>> {
+    input
         .lines()
         .map(|line| {
-            let line = line.unwrap();
             line.trim()
                 .chars()
-                .map(|c| Tile::from_char(c))
+                .map(Tile::from_char)
                 .collect::<Vec<_>>()
         })
         .collect()
 }
 
-fn find_start(tiles: &Vec<Vec<Tile>>) -> (isize, isize) {
-    for y in 0..tiles.len() {
-        match tiles[y].iter().position(|t| *t == Tile::Start) {
+fn find_start(tiles: &[Vec<Tile>]) -> (isize, isize) {
+    for (y, row) in tiles.iter().enumerate() {
+        match row.iter().position(|t| *t == Tile::Start) {
             None => {}
             Some(x) => {
                 return (y as isize, x as isize);
@@ -107,7 +110,11 @@ fn find_start(tiles: &Vec<Vec<Tile>>) -> (isize, isize) {
     panic!("Start not found");
 }
 
-fn solve_part_1(tiles: &Vec<Vec<Tile>>) -> Vec<(isize, isize)> {
+fn solve_part_1(tiles: &[Vec<Tile>], _options: &Options) -> usize {
+    find_pipe(tiles).len() / 2
+}
+
+fn find_pipe(tiles: &[Vec<Tile>]) -> Vec<(isize, isize)> {
     let coords_start = find_start(tiles);
 
     // Follow the pipe
@@ -136,18 +143,19 @@ fn solve_part_1(tiles: &Vec<Vec<Tile>>) -> Vec<(isize, isize)> {
         }
     }
 
-    println!("{}", pipe.len() / 2);
     pipe
 }
 
-fn solve_part_2(tiles: &Vec<Vec<Tile>>, pipe: Vec<(isize, isize)>) {
+fn solve_part_2(tiles: &[Vec<Tile>], _options: &Options) -> usize {
+    let pipe = find_pipe(tiles);
+
     // Transform the map in something easier to manipulate
     // Remove all unnecessary pipes
-    let mut map = tiles.clone();
-    for y in 0..tiles.len() {
-        for x in 0..tiles[0].len() {
-            if map[y][x] != Tile::None && !pipe.contains(&(y as isize, x as isize)) {
-                map[y][x] = Tile::Erased;
+    let mut map = tiles.to_vec();
+    for (y, row) in map.iter_mut().enumerate() {
+        for (x, tile) in row.iter_mut().enumerate() {
+            if *tile != Tile::None && !pipe.contains(&(y as isize, x as isize)) {
+                *tile = Tile::Erased;
             }
         }
     }
@@ -196,15 +204,15 @@ fn solve_part_2(tiles: &Vec<Vec<Tile>>, pipe: Vec<(isize, isize)>) {
 
     let mut inside_count = 0;
 
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == Tile::None || map[y][x] == Tile::Erased {
+    for row in &map {
+        for (x, tile) in row.iter().enumerate() {
+            if *tile == Tile::None || *tile == Tile::Erased {
                 // Count the number of pipes we traverse
                 let mut pipe_count = 0;
                 let mut on_border = false;
                 let mut arrived_from_top = false;
-                for x in x + 1..map[y].len() {
-                    match map[y][x] {
+                for tile in &row[x + 1..] {
+                    match tile {
                         Tile::None => {}
                         Tile::NS => pipe_count += 1,
                         Tile::EW => {}
@@ -213,13 +221,13 @@ fn solve_part_2(tiles: &Vec<Vec<Tile>>, pipe: Vec<(isize, isize)>) {
                             arrived_from_top = true;
                         }
                         Tile::NW => {
-                            if on_border && arrived_from_top == false {
+                            if on_border && !arrived_from_top {
                                 pipe_count += 1;
                                 on_border = false;
                             }
                         }
                         Tile::SW => {
-                            if on_border && arrived_from_top == true {
+                            if on_border && arrived_from_top {
                                 pipe_count += 1;
                                 on_border = false;
                             }
@@ -240,16 +248,5 @@ fn solve_part_2(tiles: &Vec<Vec<Tile>>, pipe: Vec<(isize, isize)>) {
         }
     }
 
-    println!("{}", inside_count);
-}
-
-fn main() {
-    let start_time = Instant::now();
-
-    let tiles = parse_input();
-
-    let pipe = solve_part_1(&tiles);
-    solve_part_2(&tiles, pipe);
-
-    eprintln!("{} µs", (Instant::now() - start_time).as_micros());
+    inside_count
 }
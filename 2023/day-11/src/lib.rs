@@ -1,25 +1,31 @@
-use std::io::stdin;
-use std::time::Instant;
+use aoc_core::Options;
 
-fn parse_input() -> Vec<Vec<u8>> {
-    stdin()
+aoc_core::solution! {
+    year: 2023,
+    day: 11,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
+
+fn parse_input(input: &str) -> Vec<Vec<u8>> {
+    input
         .lines()
         .map(|line| {
-            line.unwrap()
-                .chars()
+            line.chars()
                 .map(|c| if c == '#' { 1 } else { 0 })
                 .collect()
         })
         .collect()
 }
 
-fn solve_part_1(galaxy: &Vec<Vec<u8>>) {
+fn solve_part_1(galaxy: &[Vec<u8>], _options: &Options) -> isize {
     let mut coords = Vec::new();
     let mut galaxy_height = galaxy.len();
     let mut galaxy_width = galaxy[0].len();
-    for y in 0..galaxy_height {
-        for x in 0..galaxy_width {
-            if galaxy[y][x] != 0 {
+    for (y, row) in galaxy.iter().enumerate() {
+        for (x, &cell) in row.iter().enumerate() {
+            if cell != 0 {
                 coords.push((x as isize, y as isize));
             }
         }
@@ -63,16 +69,20 @@ fn solve_part_1(galaxy: &Vec<Vec<u8>>) {
         }
     }
 
-    println!("{}", sum);
+    sum
+}
+
+fn solve_part_2(galaxy: &[Vec<u8>], _options: &Options) -> isize {
+    solve_with_expansion(galaxy, 999_999)
 }
 
-fn solve_part_2(galaxy: &Vec<Vec<u8>>, expansion: isize) {
+fn solve_with_expansion(galaxy: &[Vec<u8>], expansion: isize) -> isize {
     let mut coords = Vec::new();
     let mut galaxy_height = galaxy.len() as isize;
     let mut galaxy_width = galaxy[0].len() as isize;
-    for y in 0..galaxy_height as usize {
-        for x in 0..galaxy_width as usize {
-            if galaxy[y][x] != 0 {
+    for (y, row) in galaxy.iter().enumerate() {
+        for (x, &cell) in row.iter().enumerate() {
+            if cell != 0 {
                 coords.push((x as isize, y as isize));
             }
         }
@@ -116,14 +126,5 @@ fn solve_part_2(galaxy: &Vec<Vec<u8>>, expansion: isize) {
         }
     }
 
-    println!("{}", sum);
-}
-fn main() {
-    let mut start_time = Instant::now();
-    let galaxy = parse_input();
-
-    solve_part_1(&galaxy);
-    solve_part_2(&galaxy, 999_999);
-
-    eprintln!("{} µs", (Instant::now() - start_time).as_micros());
+    sum
 }
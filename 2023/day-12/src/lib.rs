@@ -0,0 +1,146 @@
+use aoc_core::arith::{self, Number};
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::random::Rng;
+use aoc_core::Options;
+use std::io::{BufRead, Write};
+
+#[derive(Clone)]
+struct Arrangement {
+    template: String,
+    parts: Vec<usize>,
+}
+
+aoc_core::solution! {
+    year: 2023,
+    day: 12,
+    parse: parse_input,
+    part_1: solve_part_1::<usize>,
+    part_2: solve_part_2::<usize>,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+fn parse_input(input: &str) -> Vec<Arrangement> {
+    input.lines().map(parse_arrangement).collect()
+}
+
+fn parse_arrangement(line: &str) -> Arrangement {
+    let p = line.split_ascii_whitespace().collect::<Vec<_>>();
+
+    let template = p[0].to_string();
+
+    let parts = p[1].split(",").map(|p| p.parse().unwrap()).collect();
+
+    Arrangement { template, parts }
+}
+
+fn unfold(arrangement: &Arrangement) -> Arrangement {
+    let mut unfolded = arrangement.clone();
+
+    let mut template = unfolded.template.clone();
+    template += "?";
+    unfolded.template = template.repeat(5);
+    let l = unfolded.template.len();
+    unfolded.template.remove(l - 1);
+
+    let parts = unfolded.parts.clone();
+    for _ in 0..4 {
+        let mut parts = parts.clone();
+        unfolded.parts.append(&mut parts);
+    }
+
+    unfolded
+}
+
+fn solve_part_2<T: Number>(statement: &[Arrangement], options: &Options) -> T {
+    let statement = statement.iter().map(unfold).collect::<Vec<_>>();
+
+    solve_part_1(&statement, options)
+}
+
+fn count_arrangements<T: Number>(pattern: &str, groups: &[usize]) -> T {
+    let pattern = ".".to_string() + pattern.trim_end_matches('.');
+    let pattern = pattern.as_bytes();
+
+    // dp[i][j] := Number of arrangements of the first j springs into the first i locations
+    let mut dp = vec![T::zero(); pattern.len() + 1];
+    dp[0] = T::one();
+
+    for (i, _) in pattern.iter().enumerate().filter(|(_, &c)| c != b'#') {
+        dp[i + 1] = T::one();
+    }
+
+    for &group in groups {
+        let mut n_dp = vec![T::zero(); pattern.len() + 1];
+        let mut chunk = 0;
+
+        for (i, &c) in pattern.iter().enumerate() {
+            if c != b'.' {
+                chunk += 1;
+            } else {
+                chunk = 0;
+            }
+
+            if c != b'#' {
+                n_dp[i + 1] = arith::add(n_dp[i + 1].clone(), n_dp[i].clone());
+            }
+
+            if chunk >= group && pattern[i - group] != b'#' {
+                n_dp[i + 1] = arith::add(n_dp[i + 1].clone(), dp[i - group].clone());
+            }
+        }
+
+        dp = n_dp;
+    }
+
+    dp.pop().unwrap()
+}
+
+fn solve_part_1<T: Number>(statement: &[Arrangement], _options: &Options) -> T {
+    let mut sum = T::zero();
+
+    for arrangement in statement {
+        sum = arith::add(sum, count_arrangements(&arrangement.template, &arrangement.parts));
+    }
+
+    sum
+}
+
+/// Counts the arrangements of both parts row by row.
+fn stream_input(reader: &mut dyn BufRead, _options: &Options) -> [String; 2] {
+    let mut sums = [0_u128, 0_u128];
+
+    for_each_line(reader, |line| {
+        let arrangement = parse_arrangement(line);
+        let unfolded = unfold(&arrangement);
+        sums[0] = arith::add(sums[0], count_arrangements(&arrangement.template, &arrangement.parts));
+        sums[1] = arith::add(sums[1], count_arrangements(&unfolded.template, &unfolded.parts));
+    });
+
+    sums.map(|sum| sum.to_string())
+}
+
+/// Random rows of springs with at least one damaged, half of them replaced by `?`.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    generate_lines(rng, size, out, |rng, line| {
+        let length = rng.range(6, 20) as usize;
+        let mut springs = (0..length).map(|_| *rng.pick(b".#")).collect::<Vec<_>>();
+        springs[rng.below(length as u64) as usize] = b'#';
+
+        let groups = springs
+            .split(|&c| c == b'.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect::<Vec<_>>();
+
+        for spring in springs.iter_mut() {
+            if rng.below(2) == 0 {
+                *spring = b'?';
+            }
+        }
+
+        line.extend_from_slice(&springs);
+        line.push(b' ');
+        line.extend_from_slice(groups.join(",").as_bytes());
+    });
+}
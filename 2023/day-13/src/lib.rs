@@ -0,0 +1,152 @@
+use aoc_core::bitgrid::BitGrid;
+use aoc_core::parse::blocks;
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 13,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
+
+/// A pattern, as its rows and as its columns, the cells set being the rocks.
+struct Pattern {
+    rows: BitGrid<u32>,
+    columns: BitGrid<u32>,
+}
+
+/// Lines splitting `lines` into two mirrored halves that differ by exactly `smudges` cells,
+/// given as how many lines come before them, with the cells that differ as (line, position).
+fn find_mirrors(lines: &BitGrid<u32>, smudges: u32) -> Vec<(usize, Vec<(usize, usize)>)> {
+    let mut mirrors = Vec::new();
+    'split: for split in 1..lines.height() {
+        let mut differences = Vec::new();
+        for i in 0..split.min(lines.height() - split) {
+            let difference = lines.row_xor(split - i - 1, split + i);
+            if differences.len() as u32 + 2 * difference.count_ones() > 2 * smudges {
+                continue 'split;
+            }
+            for position in (0..32).filter(|&position| difference & (1 << position) != 0) {
+                differences.push((split - i - 1, position));
+                differences.push((split + i, position));
+            }
+        }
+
+        if differences.len() as u32 == 2 * smudges {
+            mirrors.push((split, differences));
+        }
+    }
+    mirrors
+}
+
+fn parse_input(input: &str) -> Vec<Pattern> {
+    blocks(input)
+        .map(|block| {
+            let rows = BitGrid::from_text(block.text, b'#');
+            let columns = rows.transpose();
+            Pattern { rows, columns }
+        })
+        .collect()
+}
+
+/// Reflection line chosen for a pattern, given as the number of columns left of it or rows above it.
+#[derive(Debug, Clone, Copy)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn score(&self) -> usize {
+        match self {
+            Reflection::Vertical(cols) => *cols,
+            Reflection::Horizontal(rows) => 100 * rows,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Reflection::Vertical(cols) => format!("vertical line after column {}", cols),
+            Reflection::Horizontal(rows) => format!("horizontal line after row {}", rows),
+        }
+    }
+}
+
+fn find_reflection(pattern: &Pattern) -> Reflection {
+    if let Some((cols, _)) = find_mirrors(&pattern.columns, 0).first() {
+        Reflection::Vertical(*cols)
+    } else if let Some((rows, _)) = find_mirrors(&pattern.rows, 0).first() {
+        Reflection::Horizontal(*rows)
+    } else {
+        panic!("not possible");
+    }
+}
+
+/// Fixes the first cell, in reading order, making a new reflection line valid: a line whose
+/// halves differ by this cell only. Returns that cell as (row, column), and the new lines.
+fn fix_smudge(pattern: &Pattern) -> ((usize, usize), Vec<Reflection>) {
+    let vertical = find_mirrors(&pattern.columns, 1)
+        .into_iter()
+        .map(|(cols, cells)| (Reflection::Vertical(cols), cells.into_iter().map(|(x, y)| (y, x)).collect::<Vec<_>>()));
+    let horizontal = find_mirrors(&pattern.rows, 1)
+        .into_iter()
+        .map(|(rows, cells)| (Reflection::Horizontal(rows), cells));
+    let candidates = vertical.chain(horizontal).collect::<Vec<_>>();
+
+    let smudge = candidates.iter().flat_map(|(_, cells)| cells.iter().copied()).min().expect("not possible");
+    let mut reflections = Vec::new();
+    for horizontal in [false, true] {
+        if let Some((reflection, _)) = candidates
+            .iter()
+            .find(|(reflection, cells)| matches!(reflection, Reflection::Horizontal(_)) == horizontal && cells.contains(&smudge))
+        {
+            reflections.push(*reflection);
+        }
+    }
+
+    (smudge, reflections)
+}
+
+fn solve_part_1(patterns: &[Pattern], options: &Options) -> usize {
+    if options.explain {
+        println!("part 1:");
+    }
+
+    let mut score = 0;
+    for (i, pattern) in patterns.iter().enumerate() {
+        let reflection = find_reflection(pattern);
+        if options.explain {
+            println!("  pattern {}: {} => {}", i + 1, reflection.describe(), reflection.score());
+        }
+        score += reflection.score();
+    }
+
+    score
+}
+
+fn solve_part_2(patterns: &[Pattern], options: &Options) -> usize {
+    if options.explain {
+        println!("part 2:");
+    }
+
+    let mut score = 0;
+    for (i, pattern) in patterns.iter().enumerate() {
+        let ((y, x), reflections) = fix_smudge(pattern);
+        for reflection in reflections {
+            if options.explain {
+                println!(
+                    "  pattern {}: smudge at row {}, column {} => {} => {}",
+                    i + 1,
+                    y + 1,
+                    x + 1,
+                    reflection.describe(),
+                    reflection.score()
+                );
+            }
+            score += reflection.score();
+        }
+    }
+
+    score
+}
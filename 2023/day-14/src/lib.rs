@@ -0,0 +1,106 @@
+use aoc_core::bitgrid::BitGrid;
+use aoc_core::Options;
+use std::collections::HashMap;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 14,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
+
+/// Round rocks move, cube rocks never do.
+struct Platform {
+    round_rocks: BitGrid,
+    cube_rocks: BitGrid,
+}
+
+#[derive(Clone, Copy)]
+enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
+
+fn solve_part_1(platform: &Platform, _options: &Options) -> usize {
+    let round_rocks = roll(&platform.round_rocks, &platform.cube_rocks, Tilt::North);
+
+    total_load(&round_rocks)
+}
+
+fn solve_part_2(platform: &Platform, _options: &Options) -> usize {
+    let mut board = spin_cycle(&platform.round_rocks, &platform.cube_rocks);
+
+    let mut previous_states = HashMap::new();
+    let mut i = 0;
+    loop {
+        previous_states.insert(board.clone(), i);
+
+        board = spin_cycle(&board, &platform.cube_rocks);
+
+        if previous_states.contains_key(&board) {
+            let j_start = previous_states[&board];
+            let j = j_start + (1000000000 - 1 - j_start) % (i + 1 - j_start);
+            board = previous_states
+                .iter()
+                .find(|&(_, k)| j == *k)
+                .unwrap()
+                .0
+                .clone();
+
+            break;
+        }
+
+        i += 1;
+    }
+
+    total_load(&board)
+}
+
+fn total_load(round_rocks: &BitGrid) -> usize {
+    round_rocks
+        .rows()
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (round_rocks.height() - i) * row.count_ones() as usize)
+        .sum()
+}
+
+fn spin_cycle(round_rocks: &BitGrid, cube_rocks: &BitGrid) -> BitGrid {
+    [Tilt::North, Tilt::West, Tilt::South, Tilt::East]
+        .into_iter()
+        .fold(round_rocks.clone(), |round_rocks, tilt| roll(&round_rocks, cube_rocks, tilt))
+}
+
+type Shift = fn(&BitGrid, usize) -> BitGrid;
+
+/// Moves every round rock whose next cell is free by one step, all at once, until none can move.
+fn roll(round_rocks: &BitGrid, cube_rocks: &BitGrid, tilt: Tilt) -> BitGrid {
+    let (forward, backward): (Shift, Shift) = match tilt {
+        Tilt::North => (BitGrid::shift_north, BitGrid::shift_south),
+        Tilt::West => (BitGrid::shift_west, BitGrid::shift_east),
+        Tilt::South => (BitGrid::shift_south, BitGrid::shift_north),
+        Tilt::East => (BitGrid::shift_east, BitGrid::shift_west),
+    };
+
+    let mut round_rocks = round_rocks.clone();
+    loop {
+        let free = (&round_rocks | cube_rocks).complement();
+        let moving = &round_rocks & &backward(&free, 1);
+        if moving.is_empty() {
+            break;
+        }
+        round_rocks = &(&round_rocks ^ &moving) | &forward(&moving, 1);
+    }
+
+    round_rocks
+}
+
+fn parse_input(input: &str) -> Platform {
+    Platform {
+        round_rocks: BitGrid::from_text(input, b'O'),
+        cube_rocks: BitGrid::from_text(input, b'#'),
+    }
+}
@@ -0,0 +1,124 @@
+use aoc_core::input::for_each_record;
+use aoc_core::random::Rng;
+use aoc_core::Options;
+use std::io::{BufRead, Write};
+use std::str::from_utf8;
+
+type Boxes = Vec<Vec<(String, usize)>>;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 15,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+fn hash(str: &str) -> usize {
+    let mut v = 0;
+
+    for c in str.chars() {
+        v += c as usize;
+        v *= 17;
+        v &= 0xFF;
+    }
+
+    v
+}
+fn solve_part_1(sequence: &[String], _options: &Options) -> usize {
+    sequence.iter().map(|str| hash(str)).sum()
+}
+
+fn solve_part_2(sequence: &[String], _options: &Options) -> usize {
+    let mut boxes = vec![Vec::new(); 256];
+
+    for op in sequence {
+        apply_operation(&mut boxes, op);
+    }
+
+    focusing_power(&boxes)
+}
+
+fn apply_operation(boxes: &mut Boxes, op: &str) {
+    if op.chars().nth(op.len() - 1).unwrap() != '-' {
+        let hash = hash(&op[0..op.len() - 2]);
+        let n = (op.chars().nth(op.len() - 1).unwrap() as u8 - b'0') as usize;
+        if let Some(pos) = boxes[hash]
+            .iter()
+            .position(|(label, _)| label == &op[0..op.len() - 2])
+        {
+            boxes[hash][pos].1 = n;
+        } else {
+            boxes[hash].push((op[0..op.len() - 2].to_string(), n));
+        }
+    } else {
+        let hash = hash(&op[0..op.len() - 1]);
+        if let Some(pos) = boxes[hash]
+            .iter()
+            .position(|(label, _)| label == &op[0..op.len() - 1])
+        {
+            boxes[hash].remove(pos);
+        }
+    }
+}
+
+fn focusing_power(boxes: &Boxes) -> usize {
+    let mut result = 0;
+    for (box_id, bx) in boxes.iter().enumerate() {
+        for (lens_id, lens) in bx.iter().enumerate() {
+            result += (box_id + 1) * (lens_id + 1) * lens.1;
+        }
+    }
+    result
+}
+
+fn parse_input(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .next()
+        .unwrap()
+        .split(',')
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Reads the steps one at a time; only the content of the boxes is kept.
+fn stream_input(reader: &mut dyn BufRead, _options: &Options) -> [String; 2] {
+    let mut hash_sum = 0_u64;
+    let mut boxes = vec![Vec::new(); 256];
+
+    for_each_record(reader, b',', |op| {
+        let op = from_utf8(op).unwrap();
+        hash_sum += hash(op) as u64;
+        apply_operation(&mut boxes, op);
+    });
+
+    [hash_sum.to_string(), focusing_power(&boxes).to_string()]
+}
+
+/// A single line of steps on labels of 2 to 4 letters.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let mut written = 0;
+    let mut step = String::new();
+
+    while written < size {
+        step.clear();
+        if written > 0 {
+            step.push(',');
+        }
+        for _ in 0..rng.range(2, 4) {
+            step.push((b'a' + rng.below(26) as u8) as char);
+        }
+        if rng.below(3) == 0 {
+            step.push('-');
+        } else {
+            step += &format!("={}", rng.range(1, 9));
+        }
+
+        out.write_all(step.as_bytes()).unwrap();
+        written += step.len() as u64;
+    }
+    writeln!(out).unwrap();
+}
@@ -1,5 +1,13 @@
-use std::collections::HashSet;
-use std::io::stdin;
+use aoc_core::bitgrid::BitGrid;
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 16,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 enum BeamDirection {
@@ -35,8 +43,23 @@ struct Solver<'a> {
     heap: Vec<BeamState>,
 }
 
+fn solve_part_1(statement: &Statement, _options: &Options) -> u32 {
+    Solver::new(statement).solve_part_1()
+}
+
+fn solve_part_2(statement: &Statement, _options: &Options) -> u32 {
+    Solver::new(statement).solve_part_2()
+}
+
 impl<'a> Solver<'a> {
-    fn solve_part_2(&mut self) {
+    fn new(statement: &'a Statement) -> Solver<'a> {
+        Solver {
+            statement,
+            heap: Vec::with_capacity(100),
+        }
+    }
+
+    fn solve_part_2(&mut self) -> u32 {
         let mut energized_max = 0;
 
         for x in 0..self.statement.width as isize {
@@ -69,37 +92,33 @@ impl<'a> Solver<'a> {
             energized_max = energized_max.max(energized);
         }
 
-        println!("{}", energized_max);
+        energized_max
     }
 
-    fn solve_part_1(&mut self) {
-        let energized = self.send_beam(BeamState {
+    fn solve_part_1(&mut self) -> u32 {
+        self.send_beam(BeamState {
             x: 0,
             y: 0,
             direction: BeamDirection::Right,
-        });
-
-        println!("{}", energized);
+        })
     }
+    /// Cells already crossed by a beam are kept per direction, their union being the energized
+    /// cells.
     fn send_beam(&mut self, initial_state: BeamState) -> u32 {
         self.heap.clear();
-
-        let mut energized = vec![false; self.statement.width * self.statement.height];
         self.heap.push(initial_state);
 
-        let mut already_visited =
-            HashSet::with_capacity(self.statement.width * self.statement.height);
+        let mut already_visited: [BitGrid; 4] =
+            std::array::from_fn(|_| BitGrid::new(self.statement.width, self.statement.height));
 
         while let Some(state) = self.heap.pop() {
-            if !already_visited.contains(&state) {
-                already_visited.insert(state);
-
-                energized[state.y as usize * self.statement.width + state.x as usize] = true;
+            if already_visited[state.direction as usize].insert(state.x as usize, state.y as usize) {
                 self.move_beam(state.x, state.y, state.direction);
             }
         }
 
-        energized.iter().map(|v| *v as u32).sum()
+        let [up, right, down, left] = &already_visited;
+        (&(up | right) | &(down | left)).count() as u32
     }
 
     fn move_beam(&mut self, x: isize, y: isize, direction: BeamDirection) {
@@ -152,12 +171,10 @@ impl<'a> Solver<'a> {
     }
 }
 
-fn parse_input() -> Statement {
-    let mirror_map: Vec<_> = stdin()
+fn parse_input(input: &str) -> Statement {
+    let mirror_map: Vec<_> = input
         .lines()
         .map(|line| {
-            let line = line.unwrap();
-
             line.chars()
                 .map(|c| match c {
                     '/' => CellKind::MirrorUp,
@@ -178,14 +195,3 @@ fn parse_input() -> Statement {
         height,
     }
 }
-
-fn main() {
-    let statement = parse_input();
-    let mut solver = Solver {
-        statement: &statement,
-        heap: Vec::with_capacity(100),
-    };
-
-    solver.solve_part_1();
-    solver.solve_part_2();
-}
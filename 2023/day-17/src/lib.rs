@@ -1,8 +1,15 @@
+use aoc_core::Options;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::hash::{Hash, Hasher};
-use std::io::stdin;
-use std::time::Instant;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 17,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
 
 struct Statement {
     width: usize,
@@ -42,11 +49,10 @@ impl PartialOrd for State {
     }
 }
 
-fn parse_input() -> Statement {
-    let map = stdin()
+fn parse_input(input: &str) -> Statement {
+    let map = input
         .lines()
         .map(|line| {
-            let line = line.unwrap();
             line.chars()
                 .map(|c| c as usize - '0' as usize)
                 .collect::<Vec<_>>()
@@ -57,19 +63,9 @@ fn parse_input() -> Statement {
 
     Statement { width, height, map }
 }
-fn main() {
-    let statement = parse_input();
-
-    let start_time = Instant::now();
-    solve_part_1(&statement);
-    eprintln!("{} ms", (Instant::now() - start_time).as_millis());
-    let start_time = Instant::now();
-    solve_part_2(&statement);
-    eprintln!("{} ms", (Instant::now() - start_time).as_millis());
-}
 
-fn solve_part_1(statement: &Statement) {
-    let a = find_min_heat(
+fn solve_part_1(statement: &Statement, _options: &Options) -> usize {
+    find_min_heat(
         statement,
         &State {
             cost: 0,
@@ -80,13 +76,12 @@ fn solve_part_1(statement: &Statement) {
         },
         1,
         4,
-    );
-
-    eprintln!("{:?}", a);
+    )
+    .unwrap()
 }
 
-fn solve_part_2(statement: &Statement) {
-    let a = find_min_heat(
+fn solve_part_2(statement: &Statement, _options: &Options) -> usize {
+    find_min_heat(
         statement,
         &State {
             cost: 0,
@@ -97,9 +92,8 @@ fn solve_part_2(statement: &Statement) {
         },
         4,
         11,
-    );
-
-    eprintln!("{:?}", a);
+    )
+    .unwrap()
 }
 
 fn find_min_heat(
@@ -1,4 +1,17 @@
-use std::io::stdin;
+use aoc_core::geometry::{self, Point2};
+use aoc_core::parse::{self, Capture, Hex, ParseError, Pattern};
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 18,
+    try_parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+    variants: [
+        part_1 "flood fill": solve_part_1_flood_fill,
+    ],
+}
 
 #[derive(Debug, Copy, Clone)]
 enum Direction {
@@ -15,36 +28,39 @@ struct Instruction {
     color: usize,
 }
 
-fn parse_input() -> Vec<Instruction> {
-    stdin()
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
-
-            let direction = match parts[0] {
-                "U" => Direction::Up,
-                "D" => Direction::Down,
-                "L" => Direction::Left,
-                _ => Direction::Right,
-            };
-
-            Instruction {
-                direction,
-                distance: parts[1].parse().unwrap(),
-                color: usize::from_str_radix(&parts[2][2..8], 16).unwrap(),
-            }
+impl Capture<'_> for Direction {
+    fn capture(text: &str) -> Result<Self, ParseError> {
+        match text {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(ParseError::invalid("U, D, L or R", text)),
+        }
+    }
+}
+
+const INSTRUCTION: Pattern = Pattern::new("{} {} (#{})");
+
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(input, |line| {
+        let (direction, distance, Hex(color)) = INSTRUCTION.parse(line)?;
+
+        Ok(Instruction {
+            direction,
+            distance,
+            color,
         })
-        .collect()
+    })
 }
 
-fn solve_part_1(instructions: &Vec<Instruction>) {
-    compute_area(instructions);
+fn solve_part_1(instructions: &[Instruction], _options: &Options) -> isize {
+    compute_area(instructions)
 }
 
-fn solve_part_2(instructions: &Vec<Instruction>) {
+fn solve_part_2(instructions: &[Instruction], _options: &Options) -> isize {
     // Decode instructions
-    let mut instructions_new = instructions.clone();
+    let mut instructions_new = instructions.to_vec();
     for instruction in instructions_new.iter_mut() {
         instruction.direction = match instruction.color & 0xF {
             0 => Direction::Right,
@@ -55,47 +71,36 @@ fn solve_part_2(instructions: &Vec<Instruction>) {
         instruction.distance = instruction.color as isize >> 4;
     }
 
-    compute_area(&instructions_new);
+    compute_area(&instructions_new)
 }
 
-fn compute_area(instructions: &Vec<Instruction>) {
-    let mut current_position = (0, 0);
-    let mut previous_position = (0, 0);
-
-    let mut area: isize = 0;
-    let mut border_length = 0;
+fn compute_area(instructions: &[Instruction]) -> isize {
+    let mut current_position = Point2::new(0, 0);
+    let mut vertices = Vec::with_capacity(instructions.len());
     for instruction in instructions.iter() {
-        match instruction.direction {
-            Direction::Up => current_position.1 -= instruction.distance,
-            Direction::Down => current_position.1 += instruction.distance,
-            Direction::Left => current_position.0 -= instruction.distance,
-            Direction::Right => current_position.0 += instruction.distance,
-        }
-        border_length += instruction.distance;
-
-        area += previous_position.0 * current_position.1 - previous_position.1 * current_position.0;
-
-        previous_position = current_position;
+        let step = match instruction.direction {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+            Direction::Right => Point2::new(1, 0),
+        };
+        current_position = current_position + step * instruction.distance as i64;
+        vertices.push(current_position);
     }
 
-    println!("{}", area / 2 + border_length / 2 + 1);
-}
-
-fn main() {
-    let ref instructions = parse_input();
-
-    solve_part_1(instructions);
-    solve_part_2(instructions);
+    // The trench is one cube wide: its cubes are both the boundary and the interior lattice points
+    (geometry::interior_points(&vertices) + geometry::boundary_points(&vertices)) as isize
 }
 
-fn solve_part_1_first_edition(instructions: &Vec<Instruction>) {
+/// First edition of part 1: draws the trench cube by cube and flood fills the outside.
+fn solve_part_1_flood_fill(instructions: &[Instruction], _options: &Options) -> usize {
     let mut border: Vec<(isize, isize)> = Vec::new();
 
     let mut current_position = (0, 0);
     let mut bounding_box = (0, 0, 0, 0);
     border.push((0, 0));
     for instruction in instructions {
-        for d in 0..instruction.distance {
+        for _ in 0..instruction.distance {
             match instruction.direction {
                 Direction::Up => current_position.1 -= 1,
                 Direction::Down => current_position.1 += 1,
@@ -150,11 +155,11 @@ fn solve_part_1_first_edition(instructions: &Vec<Instruction>) {
     }
 
     // Turn the rest to 1's
-    for i in 0..map.len() {
-        if map[i] == 0 {
-            map[i] = 1;
+    for cell in map.iter_mut() {
+        if *cell == 0 {
+            *cell = 1;
         }
     }
 
-    println!("{}", map.iter().filter(|&v| *v == 1).count());
+    map.iter().filter(|&v| *v == 1).count()
 }
@@ -0,0 +1,69 @@
+//! Sums of the ids of the games possible with many bags, with the index against checking every
+//! game, on a generated input.
+//!
+//! `cargo bench -p aoc-2023-day-2 --bench bags`
+
+use aoc_2023_day_2::bags::bag_index;
+use aoc_2023_day_2::SOLUTION;
+use aoc_core::random::Rng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: u64 = 16 << 20;
+const BAGS: usize = 1000;
+
+fn main() {
+    let mut rng = Rng::new(2023);
+    let mut input = Vec::new();
+    (SOLUTION.streaming.as_ref().unwrap().generate)(&mut rng, SIZE, &mut input);
+    let input = String::from_utf8(input).unwrap();
+
+    let index = bag_index(&input).unwrap();
+    let bags = (0..BAGS)
+        .map(|_| {
+            let cubes = [rng.range(8, 20), rng.range(8, 20), rng.range(8, 20)];
+            index.bag(&format!("{} red, {} green, {} blue", cubes[0], cubes[1], cubes[2])).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let games = index.games().count();
+    let every_game = |bags: &[Vec<usize>]| {
+        bags.iter()
+            .map(|bag| {
+                index
+                    .games()
+                    .filter(|(_, minimum)| minimum.iter().zip(bag).all(|(cubes, in_bag)| cubes <= in_bag))
+                    .map(|(id, _)| id as u64)
+                    .sum::<u64>()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(index.id_sums(&bags), every_game(&bags), "both versions must agree");
+
+    let parse_and_index = time(|| bag_index(&input).unwrap());
+    println!("{:<24} {:>8} games in {:>8.3} ms", "parse and index", games, parse_and_index.as_secs_f64() * 1000.0);
+    report("queries, index", time(|| index.id_sums(&bags)));
+    report("queries, every game", time(|| every_game(&bags)));
+}
+
+fn report(name: &str, time: Duration) {
+    println!(
+        "{:<24} {:>8} bags in {:>9.3} ms => {:>8.1} µs per bag",
+        name,
+        BAGS,
+        time.as_secs_f64() * 1000.0,
+        time.as_secs_f64() * 1e6 / BAGS as f64
+    );
+}
+
+/// Best time out of enough runs to last about a second.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_secs(1) {
+        let run_start = Instant::now();
+        black_box(f());
+        best = best.min(run_start.elapsed());
+    }
+    best
+}
@@ -0,0 +1,139 @@
+//! Which games are possible with each of many bags.
+//!
+//! A game is possible with a bag holding at least its minimum bag: the most cubes of each colour
+//! it showed in a single reveal. The index groups the games by minimum bag, with the sum of their
+//! ids, and keeps for each colour the groups sorted by their number of cubes of that colour, so
+//! that a query only checks the groups of the colour ruling out the most of them:
+//!
+//! ```
+//! use aoc_2023_day_2::bags::bag_index;
+//!
+//! let index = bag_index("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue\nGame 2: 1 blue, 2 green\nGame 3: 8 green, 6 blue, 20 red").unwrap();
+//! let bags = ["12 red, 13 green, 14 blue", "4 red, 2 green, 6 blue", "1 blue, 2 green"].map(|bag| index.bag(bag).unwrap());
+//! assert_eq!(index.id_sums(&bags), [3, 3, 2]);
+//! assert_eq!(index.ids(&bags[1]), [1, 2]);
+//! ```
+
+use aoc_core::graph::Interner;
+use aoc_core::parse::{separated, ParseError};
+use std::collections::HashMap;
+
+use crate::{parse_input, Statement, CUBES};
+
+pub struct BagIndex {
+    colors: Interner,
+    ids: Vec<usize>,
+    /// Group of each game.
+    groups: Vec<usize>,
+    /// Minimum bag of each group, indexed by colour, one after the other.
+    minimum_bags: Vec<usize>,
+    /// Games of each group, in input order, and the sum of their ids.
+    members: Vec<(Vec<usize>, u64)>,
+    /// For each colour, the groups in increasing order of their number of cubes of that colour,
+    /// and those numbers.
+    by_color: Vec<(Vec<usize>, Vec<usize>)>,
+}
+
+/// Index of the games of an input.
+pub fn bag_index(input: &str) -> Result<BagIndex, ParseError> {
+    Ok(BagIndex::of(&parse_input(input)?))
+}
+
+impl BagIndex {
+    pub(crate) fn of(statement: &Statement) -> BagIndex {
+        let colors = statement.colors.len();
+        let games = statement.games.iter().map(|game| (game.id, game.max_cubes(colors)));
+        BagIndex::new(statement.colors.clone(), games)
+    }
+
+    /// Index of games given by their id and minimum bag, indexed by the colours of `colors`.
+    pub fn new(colors: Interner, games: impl IntoIterator<Item = (usize, Vec<usize>)>) -> BagIndex {
+        let mut index = BagIndex {
+            colors,
+            ids: Vec::new(),
+            groups: Vec::new(),
+            minimum_bags: Vec::new(),
+            members: Vec::new(),
+            by_color: Vec::new(),
+        };
+
+        let mut group_of = HashMap::new();
+        for (game, (id, minimum_bag)) in games.into_iter().enumerate() {
+            assert_eq!(minimum_bag.len(), index.colors.len(), "a minimum bag has a number of cubes for each colour");
+            let group = *group_of.entry(minimum_bag).or_insert_with_key(|minimum_bag| {
+                index.minimum_bags.extend(minimum_bag);
+                index.members.push((Vec::new(), 0));
+                index.members.len() - 1
+            });
+            index.ids.push(id);
+            index.groups.push(group);
+            index.members[group].0.push(game);
+            index.members[group].1 += id as u64;
+        }
+
+        index.by_color = (0..index.colors.len())
+            .map(|color| {
+                let mut groups = (0..index.members.len()).collect::<Vec<_>>();
+                groups.sort_by_key(|&group| index.minimum_bag(group)[color]);
+                let cubes = groups.iter().map(|&group| index.minimum_bag(group)[color]).collect();
+                (groups, cubes)
+            })
+            .collect();
+
+        index
+    }
+
+    /// Bag of a text in the format of a reveal, indexed by the colours of the games. Colours no
+    /// game shows do not matter, and the bag holds no cube of the colours it does not name.
+    pub fn bag(&self, text: &str) -> Result<Vec<usize>, ParseError> {
+        let mut bag = vec![0; self.colors.len()];
+        for (count, color) in separated(text, ", ", |cubes| CUBES.parse::<(usize, &str)>(cubes))? {
+            if let Some(color) = self.colors.get(color) {
+                bag[color] = count;
+            }
+        }
+        Ok(bag)
+    }
+
+    /// Games, by id, with their minimum bag.
+    pub fn games(&self) -> impl Iterator<Item = (usize, &[usize])> {
+        self.ids.iter().zip(&self.groups).map(|(&id, &group)| (id, self.minimum_bag(group)))
+    }
+
+    fn minimum_bag(&self, group: usize) -> &[usize] {
+        let colors = self.colors.len();
+        &self.minimum_bags[group * colors..(group + 1) * colors]
+    }
+
+    /// Groups of games possible with a bag, in no particular order.
+    fn possible<'a>(&'a self, bag: &'a [usize]) -> impl Iterator<Item = usize> + 'a {
+        // Groups of the colour whose prefix of groups with few enough cubes is the shortest
+        let candidates = self
+            .by_color
+            .iter()
+            .zip(bag)
+            .map(|((groups, cubes), &in_bag)| &groups[..cubes.partition_point(|&cubes| cubes <= in_bag)])
+            .min_by_key(|groups| groups.len());
+
+        let all = candidates.is_none().then_some(0..self.members.len());
+        let candidates = candidates.into_iter().flatten().copied().chain(all.into_iter().flatten());
+        candidates.filter(move |&group| self.minimum_bag(group).iter().zip(bag).all(|(cubes, in_bag)| cubes <= in_bag))
+    }
+
+    /// Ids of the games possible with a bag, in input order.
+    pub fn ids(&self, bag: &[usize]) -> Vec<usize> {
+        let mut games = self.possible(bag).flat_map(|group| &self.members[group].0).copied().collect::<Vec<_>>();
+        games.sort_unstable();
+        games.into_iter().map(|game| self.ids[game]).collect()
+    }
+
+    /// Sum of the ids of the games possible with a bag.
+    pub fn id_sum(&self, bag: &[usize]) -> u64 {
+        self.possible(bag).map(|group| self.members[group].1).sum()
+    }
+
+    /// Sum of the ids of the games possible with each bag.
+    pub fn id_sums(&self, bags: &[Vec<usize>]) -> Vec<u64> {
+        bags.iter().map(|bag| self.id_sum(bag)).collect()
+    }
+}
@@ -0,0 +1,401 @@
+pub mod bags;
+pub mod likelihood;
+
+use aoc_core::graph::Interner;
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::parse::{self, separated, ParseError, Pattern};
+use aoc_core::random::Rng;
+use aoc_core::{Failure, Options, ParamError};
+use bags::BagIndex;
+use std::io::{BufRead, Write};
+use std::ops::Range;
+
+/// Games, and the colours their cubes come in, interned in order of first appearance.
+struct Statement {
+    colors: Interner,
+    games: Vec<Game>,
+}
+
+#[derive(Default)]
+struct Game {
+    id: usize,
+    /// Line of the game in the input.
+    line: usize,
+    reveals: Vec<Reveal>,
+}
+
+/// Cubes shown by a reveal, in the order the input names them.
+struct Reveal {
+    cubes: Vec<Cubes>,
+}
+
+/// Cubes of a single colour, as named by a reveal.
+struct Cubes {
+    color: usize,
+    count: usize,
+    /// Bytes of `<count> <colour>` in the line of the game.
+    span: Range<usize>,
+}
+
+/// Number of cubes of each colour in a bag, indexed by colour, `None` for the colours the bag does
+/// not know. Colours interned after the bag was built are unknown to it as well.
+type Bag = Vec<Option<usize>>;
+
+impl Game {
+    /// Whether the bag holds at least as many cubes of each colour as a single reveal of the game
+    /// showed, or the first colour shown by the game that the bag does not know.
+    fn is_possible(&self, bag: &Bag, colors: usize) -> Result<bool, usize> {
+        let mut possible = true;
+        for (color, cubes) in self.max_cubes(colors).into_iter().enumerate() {
+            match bag.get(color).copied().flatten() {
+                Some(in_bag) => possible &= cubes <= in_bag,
+                None if cubes > 0 => return Err(color),
+                None => {}
+            }
+        }
+        Ok(possible)
+    }
+
+    fn power(&self, colors: usize) -> usize {
+        self.max_cubes(colors).iter().product()
+    }
+
+    /// Maximum number of cubes of each colour seen in a single reveal of the game.
+    fn max_cubes(&self, colors: usize) -> Vec<usize> {
+        let mut max_cubes = vec![0; colors];
+
+        for cubes in self.reveals.iter().flat_map(|reveal| &reveal.cubes) {
+            max_cubes[cubes.color] = max_cubes[cubes.color].max(cubes.count);
+        }
+
+        max_cubes
+    }
+
+    /// Number of cubes of each colour shown by each reveal, indexed by colour.
+    fn reveal_counts(&self, colors: usize) -> Vec<Vec<usize>> {
+        self.reveals.iter().map(|reveal| reveal.counts(colors)).collect()
+    }
+
+    /// First reveal, by index, and first cubes of it, that the bag does not hold enough of.
+    /// Colours the bag does not know are left out.
+    fn first_impossible(&self, bag: &Bag) -> Option<(usize, &Cubes)> {
+        self.reveals.iter().enumerate().find_map(|(index, reveal)| {
+            let too_many = |cubes: &&Cubes| bag.get(cubes.color).copied().flatten().is_some_and(|in_bag| cubes.count > in_bag);
+            reveal.cubes.iter().find(too_many).map(|cubes| (index, cubes))
+        })
+    }
+}
+
+impl Reveal {
+    /// Number of cubes of each colour, indexed by colour, the largest count of a colour named
+    /// twice.
+    fn counts(&self, colors: usize) -> Vec<usize> {
+        let mut counts = vec![0; colors];
+        for cubes in &self.cubes {
+            counts[cubes.color] = counts[cubes.color].max(cubes.count);
+        }
+        counts
+    }
+
+    /// Cubes naming a colour already named earlier in the reveal, with the earlier ones.
+    fn duplicates(&self) -> impl Iterator<Item = (&Cubes, &Cubes)> {
+        self.cubes.iter().enumerate().filter_map(|(i, cubes)| {
+            let first = self.cubes[..i].iter().find(|earlier| earlier.color == cubes.color)?;
+            Some((first, cubes))
+        })
+    }
+}
+
+aoc_core::solution! {
+    year: 2023,
+    day: 2,
+    try_parse: parse_input,
+    part_1: solve_part1,
+    part_2: solve_part2,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+fn solve_part2(statement: &Statement, options: &Options) -> Result<usize, ParamError> {
+    let cap = estimate_cap(options)?;
+    let colors = statement.colors.len();
+    if let Some(cap) = cap {
+        for game in &statement.games {
+            check_cap(game, colors, cap)?;
+        }
+    }
+    if options.explain {
+        println!("part 2:");
+    }
+
+    Ok(statement.games.iter().map(|game| {
+        let power = game.power(colors);
+
+        if options.explain {
+            let max_cubes = game.max_cubes(colors);
+            print!("  game {}: max {} => power {}", game.id, describe_cubes(&statement.colors, &max_cubes), power);
+            if let Some(cap) = cap {
+                let (bag, likelihood) = likelihood::max_likelihood_bag(&game.reveal_counts(colors), &max_cubes, cap).unwrap();
+                let capped = if bag.contains(&cap) { ", at the cap" } else { "" };
+                print!(", most likely {} (ln L = {:.3}{})", describe_cubes(&statement.colors, &bag), likelihood, capped);
+            }
+            println!();
+        }
+
+        power
+    }).sum())
+}
+
+fn solve_part1(statement: &Statement, options: &Options) -> Result<u64, Failure> {
+    // Colours only the bag knows do not matter
+    let colors = statement.colors.len();
+    let bag = bag(options, &mut statement.colors.clone())?;
+    let cap = estimate_cap(options)?;
+
+    for game in &statement.games {
+        warn_duplicates(game, &statement.colors);
+    }
+
+    if options.explain {
+        println!("part 1:");
+    }
+
+    for game in &statement.games {
+        let possible = game
+            .is_possible(&bag, colors)
+            .map_err(|color| unknown_color(&statement.colors, color, game.line))?;
+
+        if options.explain {
+            print!("  game {}: max {} => ", game.id, describe_cubes(&statement.colors, &game.max_cubes(colors)));
+            match game.first_impossible(&bag) {
+                Some((reveal, cubes)) => print!(
+                    "impossible, reveal {} shows {} {} at column {} but the bag holds {}",
+                    reveal + 1,
+                    cubes.count,
+                    statement.colors.label(cubes.color),
+                    cubes.span.start + 1,
+                    bag[cubes.color].unwrap()
+                ),
+                None => print!("possible"),
+            }
+            if possible && cap.is_some() {
+                let bag = bag[..colors].iter().map(|cubes| cubes.unwrap_or(0)).collect::<Vec<_>>();
+                print!(" (ln L = {:.3})", likelihood::log_likelihood(&game.reveal_counts(colors), &bag));
+            }
+            println!();
+        }
+    }
+
+    // Every colour shown is known to the bag from here on
+    let bag = bag[..colors].iter().map(|cubes| cubes.unwrap_or(0)).collect::<Vec<_>>();
+    Ok(BagIndex::of(statement).id_sum(&bag))
+}
+
+/// Bag of part 1, given as `--param bag=<cubes>` in the format of a reveal, the puzzle's one by
+/// default. Its colours are interned as well.
+fn bag(options: &Options, colors: &mut Interner) -> Result<Bag, ParamError> {
+    let text = options.param("bag").unwrap_or("12 red, 13 green, 14 blue");
+    let invalid = |error| ParamError::new("bag", error);
+    let mut bag = vec![None; colors.len()];
+    for (count, color) in separated(text, ", ", |cubes| CUBES.parse::<(usize, &str)>(cubes)).map_err(invalid)? {
+        let color = intern_color(color, colors).map_err(invalid)?;
+        bag.resize(colors.len(), None);
+        bag[color] = Some(count);
+    }
+    Ok(bag)
+}
+
+/// Most cubes of a colour in a maximum-likelihood bag, given as `--param estimate=<cap>`, or 100
+/// with `--param estimate`. Without it, the explanations leave likelihoods out.
+fn estimate_cap(options: &Options) -> Result<Option<usize>, ParamError> {
+    match options.param("estimate") {
+        None => Ok(None),
+        Some("true") => Ok(Some(100)),
+        Some(cap) => match cap.parse() {
+            Ok(cap) => Ok(Some(cap)),
+            Err(_) => Err(ParamError::new("estimate", format!("invalid cap {:?}, expected a number of cubes", cap))),
+        },
+    }
+}
+
+/// Error of a cap below the cubes of a colour the game shows, so that no bag within the cap is
+/// possible.
+fn check_cap(game: &Game, colors: usize, cap: usize) -> Result<(), ParamError> {
+    match game.max_cubes(colors).into_iter().max() {
+        Some(most) if most > cap => Err(ParamError::new("estimate", format!("cap {} below the {} cubes of a colour game {} shows", cap, most, game.id))),
+        _ => Ok(()),
+    }
+}
+
+/// Warns on stderr about the colours a reveal of the game names twice. The larger count is kept.
+fn warn_duplicates(game: &Game, colors: &Interner) {
+    for (index, reveal) in game.reveals.iter().enumerate() {
+        for (first, again) in reveal.duplicates() {
+            eprintln!(
+                "warning: line {}: reveal {} names `{}` twice, at columns {} and {}, keeping the larger count",
+                game.line,
+                index + 1,
+                colors.label(again.color),
+                first.span.start + 1,
+                again.span.start + 1
+            );
+        }
+    }
+}
+
+fn unknown_color(colors: &Interner, color: usize, line: usize) -> ParseError {
+    ParseError::invalid("a colour of the bag", colors.label(color)).at_line(line)
+}
+
+fn describe_cubes(colors: &Interner, cubes: &[usize]) -> String {
+    cubes
+        .iter()
+        .enumerate()
+        .map(|(color, cubes)| format!("{} {}", cubes, colors.label(color)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+const GAME: Pattern = Pattern::new("Game {}: {}");
+const CUBES: Pattern = Pattern::new("{} {}");
+
+fn parse_input(input: &str) -> Result<Statement, ParseError> {
+    let mut colors = Interner::new();
+    let mut line_number = 0;
+    let games = parse::lines(input, |line| {
+        line_number += 1;
+        parse_game(line, line_number, &mut colors)
+    })?;
+
+    Ok(Statement { colors, games })
+}
+
+fn parse_game(line: &str, line_number: usize, colors: &mut Interner) -> Result<Game, ParseError> {
+    let (id, reveals): (usize, &str) = GAME.parse(line)?;
+
+    // The reveals end the line
+    let mut offset = line.len() - reveals.len();
+    let reveals = separated(reveals, "; ", |reveal| {
+        let start = offset;
+        offset += reveal.len() + "; ".len();
+        parse_reveal(reveal, start, colors)
+    })?;
+
+    Ok(Game { id, line: line_number, reveals })
+}
+
+/// Cubes of a reveal starting at byte `offset` of its line, such as `3 blue, 4 red`, interning
+/// new colours.
+fn parse_reveal(reveal: &str, mut offset: usize, colors: &mut Interner) -> Result<Reveal, ParseError> {
+    let cubes = separated(reveal, ", ", |text| {
+        let start = offset;
+        offset += text.len() + ", ".len();
+        let (count, color): (usize, &str) = CUBES.parse(text)?;
+        Ok(Cubes {
+            color: intern_color(color, colors)?,
+            count,
+            span: start..start + text.len(),
+        })
+    })?;
+    Ok(Reveal { cubes })
+}
+
+/// Index of a colour, made of lowercase letters.
+fn intern_color(color: &str, colors: &mut Interner) -> Result<usize, ParseError> {
+    if color.is_empty() || !color.bytes().all(|c| c.is_ascii_lowercase()) {
+        return Err(ParseError::invalid("a colour", color));
+    }
+    Ok(colors.intern(color))
+}
+
+/// Sums both parts game by game, without keeping the games in memory. A line that cannot be
+/// parsed fails both parts, a bag that cannot be parsed part 1 only, and a cap below the cubes a
+/// game shows part 2 only, as when loading.
+fn stream_input(reader: &mut dyn BufRead, options: &Options) -> [String; 2] {
+    // Likelihoods are only explained when loading, the cap is checked all the same
+    let cap = match estimate_cap(options) {
+        Ok(cap) => cap,
+        Err(error) => return [1, 2].map(|part| Failure::from(error.clone()).report(part)),
+    };
+    let mut bag_colors = Interner::new();
+    let bag_by_label = bag(options, &mut bag_colors);
+    // The bag indexed by the colours of the input, growing with them
+    let mut colors = Interner::new();
+    let mut bag = Bag::new();
+
+    let mut possible_games_id_sum = 0_u64;
+    let mut unknown: Option<ParseError> = None;
+    let mut invalid: Option<ParseError> = None;
+    let mut below_cap: Option<ParamError> = None;
+    let mut total_power = 0_u64;
+    let mut line_number = 0;
+
+    for_each_line(reader, |line| {
+        line_number += 1;
+        if invalid.is_some() {
+            return;
+        }
+        let known_colors = colors.len();
+        let game = match parse_game(line, line_number, &mut colors) {
+            Ok(game) => game,
+            Err(error) => {
+                invalid = Some(error.at_line(line_number));
+                return;
+            }
+        };
+        warn_duplicates(&game, &colors);
+        if colors.len() > known_colors {
+            // The games before did not show the new colours, so that their power is now zero
+            total_power = 0;
+            for color in known_colors..colors.len() {
+                let in_bag = bag_colors.get(colors.label(color));
+                bag.push(bag_by_label.as_ref().ok().zip(in_bag).and_then(|(bag, color)| bag[color]));
+            }
+        }
+
+        match game.is_possible(&bag, colors.len()) {
+            Ok(true) => possible_games_id_sum += game.id as u64,
+            Ok(false) => {}
+            Err(color) => {
+                unknown.get_or_insert_with(|| unknown_color(&colors, color, line_number));
+            }
+        }
+        if let (Some(cap), None) = (cap, &below_cap) {
+            below_cap = check_cap(&game, colors.len(), cap).err();
+        }
+        total_power += game.power(colors.len()) as u64;
+    });
+
+    if let Some(error) = invalid {
+        return [1, 2].map(|part| Failure::from(error.clone()).report(part));
+    }
+    let part_1 = match bag_by_label.err().map(Failure::from).or(unknown.map(Failure::from)) {
+        Some(failure) => failure.report(1),
+        None => possible_games_id_sum.to_string(),
+    };
+    let part_2 = match below_cap {
+        Some(error) => Failure::from(error).report(2),
+        None => total_power.to_string(),
+    };
+    [part_1, part_2]
+}
+
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let mut id = 0;
+    generate_lines(rng, size, out, |rng, line| {
+        id += 1;
+        let mut game = format!("Game {}: ", id);
+        for reveal in 0..rng.range(1, 6) {
+            if reveal > 0 {
+                game += "; ";
+            }
+            for (i, color) in ["red", "green", "blue"].iter().enumerate() {
+                if i > 0 {
+                    game += ", ";
+                }
+                game += &format!("{} {}", rng.range(1, 20), color);
+            }
+        }
+        line.extend_from_slice(game.as_bytes());
+    });
+}
@@ -0,0 +1,100 @@
+//! How likely the reveals of a game are under a bag, and the bag making them the most likely.
+//!
+//! Each reveal is taken as a random handful drawn from the whole bag without replacement, the
+//! cubes going back in the bag before the next one, so that a reveal follows a multivariate
+//! hypergeometric law and the reveals of a game are independent. Likelihoods are natural
+//! logarithms, `-inf` for a bag the game is impossible with:
+//!
+//! ```
+//! use aoc_2023_day_2::likelihood::{log_likelihood, max_likelihood_bag};
+//!
+//! // Two red cubes, then a red and a blue one: the minimum bag is not the most likely one
+//! let reveals = [vec![2, 0], vec![1, 1]];
+//! assert!((log_likelihood(&reveals, &[2, 1]) - (2.0_f64 / 9.0).ln()).abs() < 1e-9);
+//! assert_eq!(log_likelihood(&reveals, &[1, 1]), f64::NEG_INFINITY);
+//! assert_eq!(max_likelihood_bag(&reveals, &[2, 1], 100).unwrap().0, [3, 1]);
+//!
+//! // Adding a cube of a single colour to 7 red, 3 green and 4 blue only lowers the likelihood,
+//! // yet a larger bag is more likely
+//! let reveals = [vec![2, 0, 3], vec![4, 0, 1], vec![4, 3, 2]];
+//! let (bag, likelihood) = max_likelihood_bag(&reveals, &[4, 3, 3], 15).unwrap();
+//! assert_eq!((bag, format!("{:.3}", likelihood)), (vec![15, 5, 9], "-7.883".to_string()));
+//! assert!(log_likelihood(&reveals, &[7, 3, 4]) < -8.0);
+//! assert_eq!(max_likelihood_bag(&reveals, &[4, 3, 3], 3), None);
+//! ```
+
+/// Logarithm of the binomial coefficient `n` choose `k`.
+fn ln_choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (0..k).map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln()).sum()
+}
+
+/// Log-probability of drawing a reveal, indexed by colour, out of a bag. Colours missing from the
+/// end of the reveal were not drawn.
+pub fn log_probability(reveal: &[usize], bag: &[usize]) -> f64 {
+    if reveal.iter().skip(bag.len()).any(|&cubes| cubes > 0) {
+        return f64::NEG_INFINITY;
+    }
+    let ways = bag.iter().enumerate().map(|(color, &in_bag)| ln_choose(in_bag, reveal.get(color).copied().unwrap_or(0)));
+    ways.sum::<f64>() - ln_choose(bag.iter().sum(), reveal.iter().sum())
+}
+
+/// Log-likelihood of a bag given the reveals of a game.
+pub fn log_likelihood(reveals: &[Vec<usize>], bag: &[usize]) -> f64 {
+    reveals.iter().map(|reveal| log_probability(reveal, bag)).sum()
+}
+
+/// Bag of the highest likelihood with at most `cap` cubes of each colour, with that likelihood, or
+/// `None` when the cap is below the minimum bag.
+///
+/// The likelihood may keep growing with the bag, so that the search stops at the cap. It is a sum
+/// of a term for each colour, depending on its cubes only, and of a term depending on the total of
+/// the bag only, so that the best bag of each total is found exactly over the colours one at a time,
+/// in `cap²` steps per colour and total. Colours never shown stay out of the bag, any of their cubes
+/// only making the reveals less likely.
+pub fn max_likelihood_bag(reveals: &[Vec<usize>], minimum_bag: &[usize], cap: usize) -> Option<(Vec<usize>, f64)> {
+    if minimum_bag.iter().any(|&cubes| cubes > cap) {
+        return None;
+    }
+    let shown = (0..minimum_bag.len()).filter(|&color| minimum_bag[color] > 0).collect::<Vec<_>>();
+    let count = |reveal: &Vec<usize>, color: usize| reveal.get(color).copied().unwrap_or(0);
+
+    // Best sum of the terms of the colours so far for each total, and the cubes of each colour in it
+    let mut best: Vec<f64> = vec![0.0];
+    let mut choices = Vec::with_capacity(shown.len());
+    for &color in &shown {
+        let term = (0..=cap).map(|cubes| reveals.iter().map(|reveal| ln_choose(cubes, count(reveal, color))).sum::<f64>());
+        let term = term.collect::<Vec<_>>();
+        let mut next = vec![f64::NEG_INFINITY; best.len() + cap];
+        let mut choice = vec![0; next.len()];
+        for (total, &sum) in best.iter().enumerate().filter(|(_, sum)| sum.is_finite()) {
+            for cubes in minimum_bag[color]..=cap {
+                if sum + term[cubes] > next[total + cubes] {
+                    next[total + cubes] = sum + term[cubes];
+                    choice[total + cubes] = cubes;
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+
+    let mut best_total = None;
+    for (total, &sum) in best.iter().enumerate().filter(|(_, sum)| sum.is_finite()) {
+        let likelihood = sum - reveals.iter().map(|reveal| ln_choose(total, reveal.iter().sum())).sum::<f64>();
+        if best_total.is_none_or(|(_, best)| likelihood > best) {
+            best_total = Some((total, likelihood));
+        }
+    }
+
+    let (mut total, likelihood) = best_total?;
+    let mut bag = vec![0; minimum_bag.len()];
+    for (&color, choice) in shown.iter().zip(&choices).rev() {
+        bag[color] = choice[total];
+        total -= bag[color];
+    }
+    Some((bag, likelihood))
+}
@@ -0,0 +1,4 @@
+aoc_core::solution! {
+    year: 2023,
+    day: 24,
+}
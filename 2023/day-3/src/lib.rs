@@ -0,0 +1,280 @@
+pub mod render;
+pub mod rules;
+pub mod spans;
+
+use aoc_core::arith;
+use aoc_core::input::{for_each_record, generate_lines};
+use aoc_core::random::Rng;
+use aoc_core::{Failure, Options, ParamError};
+use render::{Class, Format};
+use rules::Rules;
+use spans::{Span, SpanIndex};
+use std::fs::File;
+use std::io::{stdout, BufRead, BufWriter, Write};
+
+/// Cells of the engine schematic, characters or bytes, and its numbers.
+struct Schematic<C = char> {
+    rows: Vec<Vec<C>>,
+    numbers: SpanIndex,
+}
+
+/// How part 1 counts a number next to several symbols, given as `--param count=<policy>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Count {
+    /// Once, the puzzle's way.
+    PerNumber,
+    /// Once for each symbol it is next to, the way the first edition did.
+    PerSymbol,
+}
+
+impl Count {
+    fn from_options(options: &Options) -> Result<Count, ParamError> {
+        match options.param("count") {
+            None | Some("number") => Ok(Count::PerNumber),
+            Some("symbol") => Ok(Count::PerSymbol),
+            Some(policy) => Err(ParamError::new("count", format!("invalid policy {:?}, expected number or symbol", policy))),
+        }
+    }
+}
+
+fn is_symbol(cell: char) -> bool {
+    cell != '.' && !cell.is_ascii_digit()
+}
+
+impl<C: Copy + Into<char>> Schematic<C> {
+    fn new(rows: Vec<Vec<C>>) -> Schematic<C> {
+        let numbers = SpanIndex::new(&rows);
+        Schematic { rows, numbers }
+    }
+
+    /// Symbols of a row, with their column.
+    fn symbols(&self, y: usize) -> impl Iterator<Item = (usize, char)> + '_ {
+        self.rows[y].iter().map(|&cell| cell.into()).enumerate().filter(|&(_, cell)| is_symbol(cell))
+    }
+
+    /// Whether a symbol is next to one of the digits of the number, diagonals included.
+    fn is_part_number(&self, number: &Span) -> bool {
+        let rows = (number.row.saturating_sub(1)..=number.row + 1).filter_map(|y| self.rows.get(y));
+        let mut cells = rows.flat_map(|row| (number.start.saturating_sub(1)..=number.end).filter_map(|x| row.get(x)));
+        cells.any(|&cell| is_symbol(cell.into()))
+    }
+
+    /// Part numbers of a row, with the column of their first digit, or of the symbol they count
+    /// for.
+    fn part_numbers(&self, y: usize, count: Count) -> Vec<(usize, u64)> {
+        match count {
+            Count::PerNumber => self
+                .numbers
+                .row(y)
+                .iter()
+                .filter(|number| self.is_part_number(number))
+                .map(|number| (number.start, number.value))
+                .collect(),
+            Count::PerSymbol => self
+                .symbols(y)
+                .flat_map(|(x, _)| self.numbers.adjacent(x, y).map(move |number| (x, number.value)))
+                .collect(),
+        }
+    }
+
+    /// What the solver makes of each cell of a row.
+    fn classes(&self, y: usize, rules: &Rules) -> Vec<Class> {
+        let applied = self.symbol_values(y, rules).into_iter().map(|(x, _)| x).collect::<Vec<_>>();
+        self.rows[y]
+            .iter()
+            .enumerate()
+            .map(|(x, &cell)| match self.numbers.span_at(x, y) {
+                Some(number) if self.is_part_number(number) => Class::PartNumber,
+                Some(_) => Class::Number,
+                None if applied.contains(&x) => Class::Applied,
+                None if is_symbol(cell.into()) => Class::Symbol,
+                None => Class::Blank,
+            })
+            .collect()
+    }
+
+    /// A row rendered with the classes of its cells.
+    fn render(&self, y: usize, rules: &Rules, format: Format) -> String {
+        format.row(self.rows[y].iter().map(|&cell| cell.into()), &self.classes(y, rules))
+    }
+
+    /// Values of the symbols of a row that a rule applies to, with their column.
+    fn symbol_values(&self, y: usize, rules: &Rules) -> Vec<(usize, u64)> {
+        self.symbols(y)
+            .filter_map(|(x, symbol)| {
+                let numbers = self.numbers.adjacent(x, y).map(|number| number.value).collect::<Vec<_>>();
+                Some((x, rules.evaluate(symbol, &numbers)?))
+            })
+            .collect()
+    }
+}
+
+aoc_core::solution! {
+    year: 2023,
+    day: 3,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+fn solve_part_2(schematic: &Schematic, options: &Options) -> Result<u64, ParamError> {
+    let rules = rules(options)?;
+    Ok((0..schematic.rows.len())
+        .flat_map(|y| schematic.symbol_values(y, &rules))
+        .map(|(_, value)| value)
+        .fold(0, arith::add))
+}
+
+/// Rules of part 2, given by the `rules` parameter, the puzzle's gears by default.
+fn rules(options: &Options) -> Result<Rules, ParamError> {
+    match options.param("rules") {
+        Some(rules) => rules.parse().map_err(|error| ParamError::new("rules", error)),
+        None => Ok(Rules::default()),
+    }
+}
+
+/// Format of the rendering of the schematic, given by the `render` parameter, none by default.
+/// `--explain` writes the rendering instead of listing what the parts found.
+fn render_format(options: &Options) -> Result<Option<Format>, ParamError> {
+    options.param("render").map(|format| format.parse().map_err(|error| ParamError::new("render", error))).transpose()
+}
+
+/// Where the rendering is written: the file given by the `render_to` parameter, so that it stays
+/// apart from the answers, or the standard output by default.
+fn render_target(options: &Options) -> Result<Box<dyn Write>, ParamError> {
+    match options.param("render_to") {
+        Some(path) => match File::create(path) {
+            Ok(file) => Ok(Box::new(BufWriter::new(file))),
+            Err(error) => Err(render_error(error)),
+        },
+        None => Ok(Box::new(stdout())),
+    }
+}
+
+fn render_error(error: std::io::Error) -> ParamError {
+    ParamError::new("render_to", format!("cannot write the rendering: {}", error))
+}
+
+fn solve_part_1(schematic: &Schematic, options: &Options) -> Result<u64, ParamError> {
+    let count = Count::from_options(options)?;
+    if let Some(format) = render_format(options)?.filter(|_| options.explain) {
+        render(&mut render_target(options)?, schematic, &rules(options)?, format).map_err(render_error)?;
+    }
+
+    Ok((0..schematic.rows.len())
+        .flat_map(|y| schematic.part_numbers(y, count))
+        .map(|(_, value)| value)
+        .fold(0, arith::add))
+}
+
+/// Writes the whole schematic, its cells coloured by what the solver made of them.
+fn render(out: &mut dyn Write, schematic: &Schematic, rules: &Rules, format: Format) -> std::io::Result<()> {
+    write!(out, "{}", format.header())?;
+    for y in 0..schematic.rows.len() {
+        writeln!(out, "{}", schematic.render(y, rules, format))?;
+    }
+    write!(out, "{}", format.footer())?;
+    out.flush()
+}
+
+/// Rendering written as the rows are done when streaming.
+struct Rendering<'a> {
+    format: Format,
+    rules: &'a Rules,
+    out: Box<dyn Write>,
+}
+
+fn parse_input(input: &str) -> Schematic {
+    Schematic::new(input.lines().map(|line| line.chars().collect()).collect())
+}
+
+/// Solves both parts with three rows of bytes in memory, each row being done once the next one is
+/// read. `--explain` shows the part numbers and the values of the symbols as they are found, or
+/// writes the rendering a row at a time. A part whose parameters are invalid is left out.
+fn stream_input(reader: &mut dyn BufRead, options: &Options) -> [String; 2] {
+    let (count, rules, format) = (Count::from_options(options), rules(options), render_format(options));
+    // The rendering shows what the rules apply to, and fails part 1 once it cannot be written
+    let mut rendering = match (&format, &rules) {
+        (Ok(Some(format)), Ok(rules)) if options.explain => {
+            Some(render_target(options).map(|out| Rendering { format: *format, rules, out }))
+        }
+        _ => None,
+    };
+    let mut window: Schematic<u8> = Schematic::new(Vec::new());
+    let mut read = 0;
+    let mut sums = [0_u64; 2];
+
+    // Does the row `y` of the window, the row `row` of the input
+    let mut process = |window: &Schematic<u8>, y: usize, row: usize| {
+        if let Some(Ok(Rendering { format, rules, out })) = &mut rendering {
+            let header = if row == 0 { format.header() } else { "" };
+            if let Err(error) = write!(out, "{}", header).and_then(|_| writeln!(out, "{}", window.render(y, rules, *format))) {
+                rendering = Some(Err(render_error(error)));
+            }
+        }
+        let part_numbers = count.as_ref().ok().map(|&count| window.part_numbers(y, count));
+        let symbol_values = rules.as_ref().ok().map(|rules| window.symbol_values(y, rules));
+        for (part, found) in [part_numbers, symbol_values].into_iter().enumerate() {
+            for (x, value) in found.into_iter().flatten() {
+                if options.explain && rendering.is_none() {
+                    println!("part {}: row {}, column {}: {}", part + 1, row + 1, x + 1, value);
+                }
+                sums[part] = arith::add(sums[part], value);
+            }
+        }
+    };
+
+    for_each_record(reader, b'\n', |line| {
+        // Reuses the buffer of the row leaving the window
+        let mut row = if window.rows.len() == 3 { window.rows.remove(0) } else { Vec::new() };
+        row.clear();
+        row.extend_from_slice(line);
+        window.rows.push(row);
+        window.numbers = SpanIndex::new(&window.rows);
+        read += 1;
+
+        // The row before the one read has all its neighbours
+        if read >= 2 {
+            process(&window, window.rows.len() - 2, read - 2);
+        }
+    });
+    if read >= 1 {
+        process(&window, window.rows.len() - 1, read - 1);
+    }
+    if let Some(Ok(Rendering { format, out, .. })) = &mut rendering {
+        let header = if read == 0 { format.header() } else { "" };
+        if let Err(error) = write!(out, "{}{}", header, format.footer()).and_then(|_| out.flush()) {
+            rendering = Some(Err(render_error(error)));
+        }
+    }
+
+    let mut answers = sums.map(|sum| sum.to_string());
+    let render_error = rendering.and_then(Result::err);
+    for (part_index, error) in [count.err().or(format.err()).or(render_error), rules.err()].into_iter().enumerate() {
+        if let Some(error) = error {
+            answers[part_index] = Failure::from(error).report(part_index + 1);
+        }
+    }
+    answers
+}
+
+/// Rows of 140 cells, as the puzzle's, of dots with numbers of up to three digits and symbols.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    const WIDTH: usize = 140;
+    const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+    generate_lines(rng, size, out, |rng, line| {
+        while line.len() < WIDTH {
+            match rng.below(10) {
+                0..=1 if line.len() + 4 <= WIDTH => {
+                    line.extend_from_slice(rng.range(1, 999).to_string().as_bytes());
+                    line.push(b'.');
+                }
+                2 => line.push(*rng.pick(SYMBOLS)),
+                _ => line.push(b'.'),
+            }
+        }
+    });
+}
@@ -0,0 +1,119 @@
+//! The schematic reprinted with its cells coloured by what the solver made of them, shown with
+//! `--explain --param render=ansi` for a terminal or `render=html` for a page, written to the file
+//! given by `--param render_to=<file>` if any.
+//!
+//! Part numbers are green, the other numbers red, symbols yellow and the symbols a rule of part 2
+//! applies to, such as gears, magenta:
+//!
+//! ```
+//! use aoc_2023_day_3::render::{Class, Format};
+//!
+//! let classes = [Class::PartNumber, Class::Blank, Class::Applied, Class::Blank, Class::Number, Class::Number];
+//! assert_eq!(
+//!     Format::Html.row("4.*&12".chars(), &classes),
+//!     "<span class=\"part\">4</span>.<span class=\"rule\">*</span>&amp;<span class=\"number\">12</span>"
+//! );
+//! ```
+
+use std::str::FromStr;
+
+/// What the solver made of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    /// A dot, or any other cell shown as is.
+    Blank,
+    /// Digit of a number next to a symbol.
+    PartNumber,
+    /// Digit of a number next to no symbol.
+    Number,
+    /// Symbol no rule applies to.
+    Symbol,
+    /// Symbol a rule of part 2 applies to.
+    Applied,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Html,
+}
+
+impl Format {
+    /// Text before the first row.
+    pub fn header(self) -> &'static str {
+        match self {
+            Format::Ansi => "",
+            Format::Html => concat!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n",
+                "pre { color: #888; }\n.part { color: #2a2; font-weight: bold; }\n.number { color: #c22; }\n",
+                ".symbol { color: #b80; }\n.rule { color: #c2c; font-weight: bold; }\n",
+                "</style>\n</head>\n<body>\n<pre>\n"
+            ),
+        }
+    }
+
+    /// Text after the last row.
+    pub fn footer(self) -> &'static str {
+        match self {
+            Format::Ansi => "",
+            Format::Html => "</pre>\n</body>\n</html>\n",
+        }
+    }
+
+    /// A row of cells with their classes, without its line break. Runs of cells of the same class
+    /// share their colour.
+    pub fn row(self, cells: impl Iterator<Item = char>, classes: &[Class]) -> String {
+        let mut row = String::new();
+        let mut current = Class::Blank;
+
+        for (cell, &class) in cells.zip(classes) {
+            if class != current {
+                row += self.close(current);
+                row += self.open(class);
+                current = class;
+            }
+            match (self, cell) {
+                (Format::Html, '&') => row += "&amp;",
+                (Format::Html, '<') => row += "&lt;",
+                (Format::Html, '>') => row += "&gt;",
+                _ => row.push(cell),
+            }
+        }
+
+        row + self.close(current)
+    }
+
+    fn open(self, class: Class) -> &'static str {
+        match (self, class) {
+            (_, Class::Blank) => "",
+            (Format::Ansi, Class::PartNumber) => "\x1b[1;32m",
+            (Format::Ansi, Class::Number) => "\x1b[31m",
+            (Format::Ansi, Class::Symbol) => "\x1b[33m",
+            (Format::Ansi, Class::Applied) => "\x1b[1;35m",
+            (Format::Html, Class::PartNumber) => "<span class=\"part\">",
+            (Format::Html, Class::Number) => "<span class=\"number\">",
+            (Format::Html, Class::Symbol) => "<span class=\"symbol\">",
+            (Format::Html, Class::Applied) => "<span class=\"rule\">",
+        }
+    }
+
+    fn close(self, class: Class) -> &'static str {
+        match (self, class) {
+            (_, Class::Blank) => "",
+            (Format::Ansi, _) => "\x1b[0m",
+            (Format::Html, _) => "</span>",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Format, String> {
+        match text {
+            "ansi" => Ok(Format::Ansi),
+            "html" => Ok(Format::Html),
+            _ => Err(format!("invalid render format {:?}, expected ansi or html", text)),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! What the symbols of the schematic make of the numbers next to them, set with
+//! `--param rules=<rules>`.
+//!
+//! Rules are separated by spaces, each written `<symbol>=<aggregation>:<arity>`: the aggregation
+//! is `product`, `sum`, `min` or `max`, and the arity `N` for symbols next to exactly `N` numbers
+//! or `N+` for at least `N`. Part 2 sums the values of the symbols a rule applies to, the puzzle's
+//! gears being `*=product:2`:
+//!
+//! ```
+//! use aoc_2023_day_3::rules::Rules;
+//!
+//! let rules = "*=product:2 #=max:1+".parse::<Rules>().unwrap();
+//! assert_eq!(rules.evaluate('*', &[467, 35]), Some(16345));
+//! assert_eq!(rules.evaluate('*', &[617]), None);
+//! assert_eq!(rules.evaluate('#', &[633, 35, 7]), Some(633));
+//! assert_eq!(rules.evaluate('+', &[592]), None);
+//!
+//! assert!("*=product:2 *=sum:1+".parse::<Rules>().is_err());
+//! ```
+
+use aoc_core::arith;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Min,
+    Max,
+}
+
+/// Number of adjacent numbers a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub symbol: char,
+    pub aggregation: Aggregation,
+    pub arity: Arity,
+}
+
+/// Rules of distinct symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules(Vec<Rule>);
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules(vec![Rule {
+            symbol: '*',
+            aggregation: Aggregation::Product,
+            arity: Arity::Exactly(2),
+        }])
+    }
+}
+
+impl Rule {
+    /// Value of the symbol next to these numbers, `None` if their count does not match the arity
+    /// or there is no minimum or maximum of no number.
+    pub fn evaluate(&self, numbers: &[u64]) -> Option<u64> {
+        let matches = match self.arity {
+            Arity::Exactly(n) => numbers.len() == n,
+            Arity::AtLeast(n) => numbers.len() >= n,
+        };
+        if !matches {
+            return None;
+        }
+
+        let numbers = numbers.iter().copied();
+        match self.aggregation {
+            Aggregation::Product => Some(numbers.fold(1, arith::mul)),
+            Aggregation::Sum => Some(numbers.fold(0, arith::add)),
+            Aggregation::Min => numbers.min(),
+            Aggregation::Max => numbers.max(),
+        }
+    }
+}
+
+impl Rules {
+    pub fn rule(&self, symbol: char) -> Option<&Rule> {
+        self.0.iter().find(|rule| rule.symbol == symbol)
+    }
+
+    /// Value of a symbol next to these numbers, `None` if no rule applies.
+    pub fn evaluate(&self, symbol: char, numbers: &[u64]) -> Option<u64> {
+        self.rule(symbol)?.evaluate(numbers)
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.0
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Rule, String> {
+        let invalid = || format!("invalid rule {:?}, expected <symbol>=<aggregation>:<arity>", text);
+        let mut chars = text.chars();
+        let symbol = chars.next().filter(|&symbol| symbol != '.' && !symbol.is_ascii_digit()).ok_or_else(invalid)?;
+        let (aggregation, arity) = chars.as_str().strip_prefix('=').and_then(|rest| rest.split_once(':')).ok_or_else(invalid)?;
+
+        let aggregation = match aggregation {
+            "product" => Aggregation::Product,
+            "sum" => Aggregation::Sum,
+            "min" => Aggregation::Min,
+            "max" => Aggregation::Max,
+            _ => return Err(invalid()),
+        };
+        let arity = match arity.strip_suffix('+') {
+            Some(n) => Arity::AtLeast(n.parse().map_err(|_| invalid())?),
+            None => Arity::Exactly(arity.parse().map_err(|_| invalid())?),
+        };
+
+        Ok(Rule { symbol, aggregation, arity })
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Rules, String> {
+        let mut rules: Vec<Rule> = Vec::new();
+        for rule in text.split_whitespace() {
+            let rule = rule.parse::<Rule>()?;
+            if rules.iter().any(|known| known.symbol == rule.symbol) {
+                return Err(format!("two rules for the symbol {:?}", rule.symbol));
+            }
+            rules.push(rule);
+        }
+        Ok(Rules(rules))
+    }
+}
@@ -0,0 +1,126 @@
+//! Numbers of the engine schematic, found once as spans of digits with a lookup from cells.
+//!
+//! ```
+//! use aoc_2023_day_3::spans::SpanIndex;
+//!
+//! let rows = ["467..114..", "...*......", "..35..633."].map(|row| row.chars().collect::<Vec<_>>());
+//! let index = SpanIndex::new(&rows);
+//! assert_eq!(index.spans().iter().map(|span| span.value).collect::<Vec<_>>(), [467, 114, 35, 633]);
+//! assert_eq!(index.span_at(1, 0).map(|span| span.value), Some(467));
+//! assert_eq!(index.row(2).iter().map(|span| span.start).collect::<Vec<_>>(), [2, 6]);
+//!
+//! // Each number touching a cell once, however many of its digits do
+//! let around = index.adjacent(3, 1).map(|span| span.value).collect::<Vec<_>>();
+//! assert_eq!(around, [467, 35]);
+//! ```
+
+use aoc_core::arith;
+
+/// Digits of a number on a row, from column `start` included to `end` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+    pub value: u64,
+}
+
+const NONE: u32 = u32::MAX;
+
+/// Every number of the schematic, and the number each cell is a digit of.
+#[derive(Debug, Clone, Default)]
+pub struct SpanIndex {
+    spans: Vec<Span>,
+    width: usize,
+    height: usize,
+    /// Index of the span of each cell, row after row, `NONE` for cells which are not digits.
+    cells: Vec<u32>,
+    /// Index of the first span of each row, and of the end of the spans.
+    row_starts: Vec<usize>,
+}
+
+impl SpanIndex {
+    /// Index of the numbers of rows of possibly different lengths, of characters or bytes.
+    pub fn new<C: Copy + Into<char>>(rows: &[impl AsRef<[C]>]) -> SpanIndex {
+        let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+        let mut index = SpanIndex {
+            spans: Vec::new(),
+            width,
+            height: rows.len(),
+            cells: vec![NONE; width * rows.len()],
+            row_starts: Vec::with_capacity(rows.len() + 1),
+        };
+
+        for (y, row) in rows.iter().enumerate() {
+            index.row_starts.push(index.spans.len());
+            let row = row.as_ref();
+            let digit = |x: usize| row.get(x).and_then(|&cell| cell.into().to_digit(10));
+            let mut x = 0;
+            while x < row.len() {
+                if digit(x).is_none() {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                let mut value = 0_u64;
+                while let Some(digit) = digit(x) {
+                    value = arith::add(arith::mul(value, 10), digit as u64);
+                    index.cells[y * width + x] = index.spans.len() as u32;
+                    x += 1;
+                }
+                index.spans.push(Span { row: y, start, end: x, value });
+            }
+        }
+        index.row_starts.push(index.spans.len());
+
+        index
+    }
+
+    /// Numbers in reading order.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Numbers of a row, from left to right.
+    pub fn row(&self, y: usize) -> &[Span] {
+        match self.row_starts.get(y..y + 2) {
+            Some(&[start, end]) => &self.spans[start..end],
+            _ => &[],
+        }
+    }
+
+    /// Number the cell is a digit of.
+    pub fn span_at(&self, x: usize, y: usize) -> Option<&Span> {
+        self.span_index_at(x, y).map(|span| &self.spans[span])
+    }
+
+    fn span_index_at(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        match self.cells[y * self.width + x] {
+            NONE => None,
+            span => Some(span as usize),
+        }
+    }
+
+    /// Distinct numbers next to a cell, diagonals included, in reading order.
+    pub fn adjacent(&self, x: usize, y: usize) -> impl Iterator<Item = &Span> {
+        // At most three numbers above, three below, and one on each side
+        let (mut spans, mut count) = ([0; 8], 0);
+        for ny in y.saturating_sub(1)..=y + 1 {
+            for nx in x.saturating_sub(1)..=x + 1 {
+                match self.span_index_at(nx, ny) {
+                    // The cells of a number follow each other, the cell itself left aside
+                    Some(span) if (nx, ny) != (x, y) && (count == 0 || spans[count - 1] != span) => {
+                        spans[count] = span;
+                        count += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        spans.into_iter().take(count).map(|span| &self.spans[span])
+    }
+}
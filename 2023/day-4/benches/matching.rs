@@ -0,0 +1,102 @@
+//! Matching numbers with 128-bit sets, sorted merges and the first edition's scans, on millions of
+//! generated cards, on as many cards of the puzzle's size, then on the generated cards with numbers
+//! too large for the sets, on long cards, and on cards of growing lengths around the number of
+//! comparisons beyond which `matches` merges sorted lists rather than scanning.
+//!
+//! `cargo bench -p aoc-2023-day-4 --bench matching`
+
+use aoc_2023_day_4::matching::{bitset, matches, scan, sorted_merge};
+use aoc_2023_day_4::SOLUTION;
+use aoc_core::random::Rng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: u64 = 128 << 20;
+const PUZZLE_CARDS: usize = 1_000_000;
+const LONG_CARDS: usize = 20_000;
+const LIMIT_CARDS: usize = 20_000;
+/// Lengths of the winning numbers and of the numbers, their products around the scan limit.
+const LIMIT_LENGTHS: [(usize, usize); 6] = [(32, 32), (64, 64), (64, 128), (128, 128), (128, 256), (256, 256)];
+
+type Cards = Vec<(Vec<usize>, Vec<usize>)>;
+
+fn main() {
+    let mut input = Vec::new();
+    (SOLUTION.streaming.as_ref().unwrap().generate)(&mut Rng::new(2023), SIZE, &mut input);
+    let input = String::from_utf8(input).unwrap();
+
+    let list = |text: &str| text.split_ascii_whitespace().map(|number| number.parse().unwrap()).collect::<Vec<usize>>();
+    let cards: Cards = input
+        .lines()
+        .map(|line| {
+            let (winning_numbers, numbers) = line.split_once(": ").unwrap().1.split_once(" | ").unwrap();
+            (list(winning_numbers), list(numbers))
+        })
+        .collect();
+    let large: Cards = cards
+        .iter()
+        .map(|(winning_numbers, numbers)| {
+            let scale = |list: &Vec<usize>| list.iter().map(|number| number * 1000).collect();
+            (scale(winning_numbers), scale(numbers))
+        })
+        .collect();
+    let mut rng = Rng::new(2023);
+    let mut random_cards = |cards: usize, winning_numbers: usize, numbers: usize, largest: i64| -> Cards {
+        let mut list = |length| (0..length).map(|_| rng.range(1, largest) as usize).collect();
+        (0..cards).map(|_| (list(winning_numbers), list(numbers))).collect()
+    };
+    let puzzle = random_cards(PUZZLE_CARDS, 10, 25, 99);
+    let long = random_cards(LONG_CARDS, 100, 500, 2000);
+    let around_limit = LIMIT_LENGTHS.map(|(winning_numbers, numbers)| random_cards(LIMIT_CARDS, winning_numbers, numbers, 10_000));
+
+    let count = |cards: &Cards, matcher: fn(&[usize], &[usize]) -> usize| {
+        cards.iter().map(|(winning_numbers, numbers)| matcher(winning_numbers, numbers)).sum::<usize>()
+    };
+    let bitset = |winning_numbers: &[usize], numbers: &[usize]| bitset(winning_numbers, numbers).unwrap();
+    assert_eq!(count(&cards, bitset), count(&cards, scan), "all versions must agree");
+    assert_eq!(count(&cards, sorted_merge), count(&cards, scan), "all versions must agree");
+    assert_eq!(count(&large, matches), count(&cards, scan), "all versions must agree");
+    assert_eq!(count(&puzzle, bitset), count(&puzzle, scan), "all versions must agree");
+    assert_eq!(count(&long, matches), count(&long, scan), "all versions must agree");
+    assert_eq!(count(&long, sorted_merge), count(&long, scan), "all versions must agree");
+    for cards in &around_limit {
+        assert_eq!(count(cards, sorted_merge), count(cards, scan), "all versions must agree");
+    }
+
+    report("bitset", cards.len(), time(|| count(&cards, bitset)));
+    report("sorted merge", cards.len(), time(|| count(&cards, sorted_merge)));
+    report("scan", cards.len(), time(|| count(&cards, scan)));
+    report("puzzle size, bitset", puzzle.len(), time(|| count(&puzzle, bitset)));
+    report("puzzle size, scan", puzzle.len(), time(|| count(&puzzle, scan)));
+    report("large numbers, matches", large.len(), time(|| count(&large, matches)));
+    report("large numbers, merge", large.len(), time(|| count(&large, sorted_merge)));
+    report("long cards, matches", long.len(), time(|| count(&long, matches)));
+    report("long cards, scan", long.len(), time(|| count(&long, scan)));
+    for ((winning_numbers, numbers), cards) in LIMIT_LENGTHS.iter().zip(&around_limit) {
+        let lengths = format!("{}x{}", winning_numbers, numbers);
+        report(&format!("{}, scan", lengths), cards.len(), time(|| count(cards, scan)));
+        report(&format!("{}, merge", lengths), cards.len(), time(|| count(cards, sorted_merge)));
+    }
+}
+
+fn report(name: &str, cards: usize, time: Duration) {
+    println!(
+        "{:<24} {:>8} cards in {:>8.3} ms => {:>6.1} ns per card",
+        name,
+        cards,
+        time.as_secs_f64() * 1000.0,
+        time.as_secs_f64() * 1e9 / cards as f64
+    );
+}
+
+/// Best time out of enough runs to last about a second.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_secs(1) {
+        let run_start = Instant::now();
+        black_box(f());
+        best = best.min(run_start.elapsed());
+    }
+    best
+}
@@ -0,0 +1,240 @@
+pub mod matching;
+
+use aoc_core::arith::{self, Number};
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::parse::{self, ParseError, Pattern};
+use aoc_core::random::Rng;
+use aoc_core::{Failure, Options, ParamError};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Write};
+
+aoc_core::solution! {
+    year: 2023,
+    day: 4,
+    try_parse: parse_input,
+    part_1: solve_part_1::<i32>,
+    part_2: solve_part_2::<usize>,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+/// What part 2 does with the copies a card wins of cards that do not exist, past the last card or
+/// in a gap between ids, given as `--param copies=<policy>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Copies {
+    /// They are not counted.
+    Clip,
+    /// The part fails, naming the first such card.
+    Error,
+}
+
+impl Copies {
+    fn from_options(options: &Options) -> Result<Copies, ParamError> {
+        match options.param("copies") {
+            None | Some("clip") => Ok(Copies::Clip),
+            Some("error") => Ok(Copies::Error),
+            Some(policy) => Err(ParamError::new("copies", format!("invalid policy {:?}, expected clip or error", policy))),
+        }
+    }
+}
+
+fn missing_card(id: usize) -> ParseError {
+    ParseError::invalid("a card to win a copy of", &format!("card {}", id))
+}
+
+/// Cards win copies of the cards with the next ids, whatever the order of the input, so that they
+/// are counted in order of id.
+fn solve_part_2<T: Number>(cards: &[Card], options: &Options) -> Result<T, Failure> {
+    let policy = Copies::from_options(options)?;
+    let mut index_of = HashMap::with_capacity(cards.len());
+    for (index, card) in cards.iter().enumerate() {
+        if index_of.insert(card.id, index).is_some() {
+            return Err(ParseError::invalid("a card id used once", &card.id.to_string()).at_line(card.line).into());
+        }
+    }
+    let mut order = (0..cards.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&index| cards[index].id);
+
+    let mut card_count = vec![T::one(); cards.len()];
+
+    if options.explain {
+        println!("part 2:");
+    }
+
+    for index in order {
+        let card = &cards[index];
+        let winning_number_count = card.winning_number_count;
+        let mut clipped = 0;
+
+        for won in card.id + 1..=card.id + winning_number_count {
+            match (index_of.get(&won), policy) {
+                (Some(&won), _) => card_count[won] = arith::add(card_count[won].clone(), card_count[index].clone()),
+                (None, Copies::Clip) => clipped += 1,
+                (None, Copies::Error) => return Err(missing_card(won).at_line(card.line).into()),
+            }
+        }
+
+        if options.explain {
+            print!("  card {}: {} matches, {} copies", card.id, winning_number_count, card_count[index]);
+            if winning_number_count > 0 {
+                print!(" => each copy wins one copy of cards {}..={}", card.id + 1, card.id + winning_number_count);
+            }
+            if clipped > 0 {
+                print!(", {} of which do not exist", clipped);
+            }
+            println!();
+        }
+    }
+
+    Ok(arith::sum(card_count))
+}
+
+fn solve_part_1<T: Number>(cards: &[Card], options: &Options) -> T {
+    if options.explain {
+        println!("part 1:");
+    }
+
+    arith::sum(cards.iter().map(|card| {
+        let winning_number_count = card.winning_number_count as u32;
+        let points = if winning_number_count > 0 {
+            arith::pow(T::one() + T::one(), winning_number_count - 1)
+        }
+        else {
+            T::zero()
+        };
+
+        if options.explain {
+            println!("  card {}: {} matches => {} points", card.id, winning_number_count, points);
+        }
+
+        points
+    }))
+}
+
+struct Card {
+    id: usize,
+    /// Line of the card in the input.
+    line: usize,
+    /// Number of its winning numbers the card has.
+    winning_number_count: usize,
+}
+
+const CARD: Pattern = Pattern::new("{}: {} | {}");
+
+fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    let mut line_number = 0;
+    parse::lines(input, |line| {
+        line_number += 1;
+        parse_card(line, line_number)
+    })
+}
+
+fn parse_card(line: &str, line_number: usize) -> Result<Card, ParseError> {
+    let (header, winning_numbers, numbers): (&str, Vec<usize>, Vec<usize>) = CARD.parse(line)?;
+
+    Ok(Card {
+        id: parse_card_id(header)?,
+        line: line_number,
+        winning_number_count: matching::matches(&winning_numbers, &numbers),
+    })
+}
+
+/// Id of a card header, `Card` followed by any number of spaces and the id, or the id alone.
+fn parse_card_id(header: &str) -> Result<usize, ParseError> {
+    let id = header.trim_start().strip_prefix("Card").unwrap_or(header);
+    id.trim().parse().map_err(|_| ParseError::invalid("a card header such as `Card 1`", header))
+}
+
+/// Solves both parts card by card. The copies won for the next cards are the only state kept,
+/// so memory is bounded by the largest number of matches of a card, not by the number of cards.
+/// Ids may skip values but must increase, the cards being counted in order: a card out of order
+/// fails part 2, a line that cannot be parsed both parts.
+fn stream_input(reader: &mut dyn BufRead, options: &Options) -> [String; 2] {
+    let policy = Copies::from_options(options);
+    let mut score = 0_u64;
+    let mut card_count = 0_u64;
+    let mut pending_copies: VecDeque<u64> = VecDeque::new();
+    let mut previous_id = None;
+    let mut error: Option<ParseError> = None;
+    let mut invalid: Option<ParseError> = None;
+    let mut line_number = 0;
+
+    // Drops the copies won of the cards from `from` to `to` excluded, which do not exist. The card
+    // which won them is not known anymore.
+    let skip = |pending_copies: &mut VecDeque<u64>, from: usize, to: usize, error: &mut Option<ParseError>| {
+        for missing in from..to {
+            match pending_copies.pop_front() {
+                Some(copies) if copies > 0 && policy == Ok(Copies::Error) => {
+                    error.get_or_insert_with(|| missing_card(missing));
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+    };
+
+    for_each_line(reader, |line| {
+        line_number += 1;
+        if invalid.is_some() {
+            return;
+        }
+        let card = match parse_card(line, line_number) {
+            Ok(card) => card,
+            Err(parse_error) => {
+                invalid = Some(parse_error.at_line(line_number));
+                return;
+            }
+        };
+        let winning_number_count = card.winning_number_count;
+
+        if winning_number_count > 0 {
+            score = arith::add(score, arith::pow(2, winning_number_count as u32 - 1));
+        }
+
+        // Part 2 is given up from the first card out of order
+        match previous_id {
+            Some(previous_id) if card.id <= previous_id => {
+                let found = format!("card {} after card {}", card.id, previous_id);
+                error.get_or_insert_with(|| ParseError::invalid("increasing card ids", &found).at_line(line_number));
+                return;
+            }
+            Some(previous_id) => skip(&mut pending_copies, previous_id + 1, card.id, &mut error),
+            None => {}
+        }
+        previous_id = Some(card.id);
+
+        let copies = arith::add(1, pending_copies.pop_front().unwrap_or(0));
+        card_count = arith::add(card_count, copies);
+
+        if pending_copies.len() < winning_number_count {
+            pending_copies.resize(winning_number_count, 0);
+        }
+        for pending in pending_copies.iter_mut().take(winning_number_count) {
+            *pending = arith::add(*pending, copies);
+        }
+    });
+    if let Some(last_id) = previous_id {
+        skip(&mut pending_copies, last_id + 1, usize::MAX, &mut error);
+    }
+
+    if let Some(invalid) = invalid {
+        return [1, 2].map(|part| Failure::from(invalid.clone()).report(part));
+    }
+    let part_2 = match policy.err().map(Failure::from).or(error.map(Failure::from)) {
+        Some(failure) => failure.report(2),
+        None => card_count.to_string(),
+    };
+    [score.to_string(), part_2]
+}
+
+/// Cards with 5 winning numbers and 8 numbers out of 99, so that copies stay rare enough for the
+/// card count not to grow exponentially.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let mut id = 0;
+    generate_lines(rng, size, out, |rng, line| {
+        id += 1;
+        let winning_numbers = (0..5).map(|_| rng.range(1, 99).to_string()).collect::<Vec<_>>();
+        let numbers = (0..8).map(|_| rng.range(1, 99).to_string()).collect::<Vec<_>>();
+        line.extend_from_slice(format!("Card {}: {} | {}", id, winning_numbers.join(" "), numbers.join(" ")).as_bytes());
+    });
+}
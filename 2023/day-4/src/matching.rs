@@ -0,0 +1,77 @@
+//! Counts the winning numbers of a card found among its numbers.
+//!
+//! Numbers below 128 are looked up in a 128-bit set of the card's numbers. Larger ones are searched
+//! for in the list on cards short enough for sorting to cost more than that, and found by merging
+//! both lists once sorted on longer ones. A winning number written twice counts twice, as it did
+//! when each one was searched for in the list:
+//!
+//! ```
+//! use aoc_2023_day_4::matching::{bitset, matches, scan, sorted_merge};
+//!
+//! let (winning, numbers) = ([41, 48, 83, 86, 17], [83, 86, 6, 31, 17, 9, 48, 53]);
+//! assert_eq!(matches(&winning, &numbers), 4);
+//! assert_eq!(bitset(&winning, &numbers), Some(4));
+//! assert_eq!(sorted_merge(&winning, &numbers), 4);
+//! assert_eq!(scan(&winning, &numbers), 4);
+//!
+//! assert_eq!(bitset(&[200, 7], &[7, 200]), None);
+//! assert_eq!(matches(&[200, 7, 7], &[7, 200]), 3);
+//! ```
+
+/// Comparisons of a scan beyond which sorting both lists pays off: the `matching` benchmark has
+/// scans faster on cards of 64 and 128 numbers, merges on cards of 128 and 128.
+const SCAN_LIMIT: usize = 8192;
+
+/// Number of winning numbers among the numbers.
+pub fn matches(winning_numbers: &[usize], numbers: &[usize]) -> usize {
+    bitset(winning_numbers, numbers).unwrap_or_else(|| {
+        if winning_numbers.len() * numbers.len() <= SCAN_LIMIT {
+            scan(winning_numbers, numbers)
+        } else {
+            sorted_merge(winning_numbers, numbers)
+        }
+    })
+}
+
+/// Number of winning numbers among the numbers, `None` if a number is 128 or more.
+pub fn bitset(winning_numbers: &[usize], numbers: &[usize]) -> Option<usize> {
+    let mut set = 0_u128;
+    for &number in numbers {
+        if number >= 128 {
+            return None;
+        }
+        set |= 1 << number;
+    }
+
+    let mut count = 0;
+    for &number in winning_numbers {
+        if number >= 128 {
+            return None;
+        }
+        count += (set >> number & 1) as usize;
+    }
+    Some(count)
+}
+
+/// Number of winning numbers among the numbers, both lists being sorted then walked together.
+pub fn sorted_merge(winning_numbers: &[usize], numbers: &[usize]) -> usize {
+    let (mut winning_numbers, mut numbers) = (winning_numbers.to_vec(), numbers.to_vec());
+    winning_numbers.sort_unstable();
+    numbers.sort_unstable();
+
+    let mut count = 0;
+    let mut numbers = numbers.into_iter().peekable();
+    for number in winning_numbers {
+        while numbers.next_if(|&other| other < number).is_some() {}
+        if numbers.peek() == Some(&number) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Number of winning numbers among the numbers, each one searched for in the list, as the first
+/// edition did.
+pub fn scan(winning_numbers: &[usize], numbers: &[usize]) -> usize {
+    winning_numbers.iter().filter(|number| numbers.contains(number)).count()
+}
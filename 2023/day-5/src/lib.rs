@@ -1,4 +1,12 @@
-use std::io::stdin;
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 5,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+}
 
 #[derive(Default)]
 struct Data {
@@ -6,16 +14,16 @@ struct Data {
     locations_maps: Vec<Vec<[u128; 3]>>,
 }
 
-fn parse_input() -> Data {
+fn parse_input(input: &str) -> Data {
     let mut data = Data::default();
 
-    let mut lines = stdin().lines();
+    let mut lines = input.lines();
 
-    let line = lines.next().unwrap().unwrap();
+    let line = lines.next().unwrap();
     let seeds_str = line.split_ascii_whitespace().collect::<Vec<_>>();
     data.seeds = seeds_str[1..].iter().map(|&p| p.parse().unwrap()).collect();
 
-    lines.next().unwrap().unwrap();
+    lines.next().unwrap();
 
     let mut state = 0;
     let mut location_map = Vec::new();
@@ -31,7 +39,6 @@ fn parse_input() -> Data {
                         state = 1;
                     }
                     _ => {
-                        let line = line.unwrap();
                         if line.is_empty() {
                             data.locations_maps.push(location_map);
                             location_map = Vec::new();
@@ -52,7 +59,7 @@ fn parse_input() -> Data {
     data
 }
 
-fn solve_part_1(data: &Data) {
+fn solve_part_1(data: &Data, _options: &Options) -> u128 {
     let mut lowest_location_number = u128::MAX;
 
     for &seed in &data.seeds {
@@ -69,17 +76,17 @@ fn solve_part_1(data: &Data) {
         lowest_location_number = lowest_location_number.min(current_location);
     }
 
-    println!("{}", lowest_location_number);
+    lowest_location_number
 }
 
-fn solve_part_2(data: &Data) {
+fn solve_part_2(data: &Data, _options: &Options) -> u128 {
     let mut lowest_location_number = u128::MAX;
 
     for i in (0..data.seeds.len()).step_by(2) {
         lowest_location_number = lowest_location_number.min(get_range_lowest_location(data, (data.seeds[i], data.seeds[i] + data.seeds[i + 1] - 1), 0));
     }
 
-    println!("{}", lowest_location_number);
+    lowest_location_number
 }
 
 fn get_range_lowest_location(data: &Data, range: (u128, u128), location_index: usize) -> u128 {
@@ -120,12 +127,5 @@ fn get_range_lowest_location(data: &Data, range: (u128, u128), location_index: u
         }
     }
 
-    return get_range_lowest_location(data, range, location_index + 1);
+    get_range_lowest_location(data, range, location_index + 1)
 }
-
-fn main() {
-    let data = parse_input();
-
-    solve_part_1(&data);
-    solve_part_2(&data);
-}
\ No newline at end of file
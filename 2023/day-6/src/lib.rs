@@ -0,0 +1,225 @@
+use aoc_core::arith::{self, Number};
+use aoc_core::input::for_each_token;
+use aoc_core::numeric::BigInt;
+use aoc_core::parse::ParseError;
+use aoc_core::random::Rng;
+use aoc_core::{Failure, Options};
+use std::io::{BufRead, Write};
+
+aoc_core::solution! {
+    year: 2023,
+    day: 6,
+    parse: parse_input,
+    part_1: solve_part_1::<i32>,
+    part_2: solve_part_2::<i32>,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+struct Races {
+    times: Vec<BigInt>,
+    distances: Vec<BigInt>,
+}
+
+fn parse_input(input: &str) -> Races {
+    let mut lines = input.lines().map(|line| {
+        line.split_ascii_whitespace()
+            .skip(1)
+            .map(|p| p.parse().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    Races {
+        times: lines.next().unwrap(),
+        distances: lines.next().unwrap(),
+    }
+}
+
+fn solve_part_1<T: Number + TryFrom<BigInt>>(races: &Races, _options: &Options) -> T {
+    solve(&races.times, &races.distances)
+}
+
+fn solve_part_2<T: Number + TryFrom<BigInt>>(races: &Races, _options: &Options) -> T {
+    // The spaces between the numbers are to be ignored
+    let t = vec![concat_values(&races.times)];
+    let d = vec![concat_values(&races.distances)];
+    solve(&t, &d)
+}
+
+fn concat_values(values: &[BigInt]) -> BigInt {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+/// The problem can be written in the form of a simple 2nd order polynom:
+/// with :
+///   T = time allowed
+///   R = distance to beat
+///   v = speed of the boat
+/// we have the following equation system:
+///   t = T - v = travel time
+///   d = v * t
+///   d > R
+///
+/// => -v² + T*v - R > 0
+///
+/// So all the valid values of the problem are the integer values between the two root of this polynom.
+///
+fn solve<T: Number + TryFrom<BigInt>>(t: &[BigInt], d: &[BigInt]) -> T {
+    let mut result = T::one();
+
+    for i in 0..t.len() {
+        result = arith::mul(result, count_ways(&t[i], &d[i]));
+    }
+
+    result
+}
+
+/// The roots are computed exactly, with big integers, as floats lose the precision needed above
+/// 2^53. The integer square root of the discriminant gives the smallest winning speed up to one,
+/// which is then adjusted, and the largest one is its mirror `T - v`.
+fn count_ways<T: Number + TryFrom<BigInt>>(t: &BigInt, d: &BigInt) -> T {
+    let one = BigInt::one();
+    let two = BigInt::from(2);
+    let beats_record = |v: &BigInt| &(v * &(t - v)) > d;
+
+    let discriminant = &(t * t) - &(&BigInt::from(4) * d);
+    if discriminant.is_negative() {
+        return T::zero();
+    }
+
+    let half = t.div_floor(&two);
+    let mut v_min = (t - &discriminant.isqrt()).div_floor(&two);
+    while v_min <= half && !beats_record(&v_min) {
+        v_min = &v_min + &one;
+    }
+    if v_min > half {
+        return T::zero();
+    }
+    while beats_record(&(&v_min - &one)) {
+        v_min = &v_min - &one;
+    }
+
+    arith::convert(&(t - &(&two * &v_min)) + &one)
+}
+
+/// Part 2 is quadratic in the number of digits of its single race, so it is only solved up to
+/// this many digits when streaming arbitrarily large inputs.
+const STREAM_PART_2_MAX_DIGITS: usize = 4096;
+
+/// Reads the input token by token. The times have to be kept until their distances are read, so
+/// memory grows with the number of races, but not with the size of their text. A token which is
+/// not a number, or a time without its distance or the other way round, fails both parts.
+fn stream_input(reader: &mut dyn BufRead, _options: &Options) -> [String; 2] {
+    let mut times = Vec::new();
+    let mut reading_distances = false;
+    let mut race = 0;
+    let mut error: Option<ParseError> = None;
+
+    let mut product = Some(1_u128);
+    let mut time_digits = String::new();
+    let mut distance_digits = String::new();
+
+    for_each_token(reader, |token| {
+        if error.is_some() {
+            return;
+        }
+        let number = match token {
+            "Time:" => return,
+            "Distance:" => {
+                reading_distances = true;
+                return;
+            }
+            _ if race == times.len() && reading_distances => Err(ParseError::invalid("a time for each distance", token)),
+            _ => parse_number(token),
+        };
+        match number {
+            Err(number_error) => error = Some(number_error),
+            Ok(time) if !reading_distances => {
+                times.push(time);
+                if time_digits.len() <= STREAM_PART_2_MAX_DIGITS {
+                    time_digits.push_str(token);
+                }
+            }
+            Ok(distance) => {
+                let ways: u128 = count_ways(&times[race], &distance);
+                product = product.and_then(|product| product.checked_mul(ways));
+                if distance_digits.len() <= STREAM_PART_2_MAX_DIGITS {
+                    distance_digits.push_str(token);
+                }
+                race += 1;
+            }
+        }
+    });
+    if error.is_none() && (times.is_empty() || race < times.len()) {
+        error = Some(ParseError::missing("a distance for each time"));
+    }
+    if let Some(error) = error {
+        return [1, 2].map(|part| Failure::from(error.clone()).report(part));
+    }
+
+    // Both are made of the digits of valid numbers, and complete when under the limit
+    let part_2 = if time_digits.len().max(distance_digits.len()) <= STREAM_PART_2_MAX_DIGITS {
+        count_ways::<BigInt>(&time_digits.parse().unwrap(), &distance_digits.parse().unwrap()).to_string()
+    } else {
+        "too large".to_string()
+    };
+    [product.map_or("overflow".to_string(), |product| product.to_string()), part_2]
+}
+
+/// A number of the input, of digits only so that part 2 can join them.
+fn parse_number(token: &str) -> Result<BigInt, ParseError> {
+    let invalid = || ParseError::invalid("a number", token);
+    if !token.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    token.parse().map_err(|_| invalid())
+}
+
+/// Races whose record can always be beaten, all on two lines of roughly `size / 2` bytes each.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let race_count = (size / 10).max(1);
+    let mut distance_rng = Rng::new(rng.next_u64());
+
+    // Times are drawn twice from the same sequence, to know them again when writing the distances
+    let mut times_rng = rng.clone();
+    write!(out, "Time:").unwrap();
+    for _ in 0..race_count {
+        write!(out, " {}", rng.range(10, 99)).unwrap();
+    }
+    write!(out, "\nDistance:").unwrap();
+    for _ in 0..race_count {
+        let t = times_rng.range(10, 99);
+        write!(out, " {}", distance_rng.range(0, t * t / 4 - 1)).unwrap();
+    }
+    writeln!(out).unwrap();
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(input: &str) -> [String; 2] {
+        stream_input(&mut input.as_bytes(), &Options::default())
+    }
+
+    #[test]
+    fn streaming_matches_loading() {
+        // Times and distances of different lengths, so that neither can stand for both
+        let inputs = ["Time: 7 15 30\nDistance: 9 40 200\n", "Time: 71 53\nDistance: 940 2000\n", "Time: 1234 567\nDistance: 9 8\n"];
+        for input in inputs {
+            let part_2 = solve_part_2::<u64>(&parse_input(input), &Options::default());
+            assert_eq!(stream(input)[1], part_2.to_string(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn streaming_part_2_past_the_limit() {
+        // The times are short enough, not the distances
+        let long = "9".repeat(STREAM_PART_2_MAX_DIGITS + 1);
+        assert_eq!(stream(&format!("Time: 7 15\nDistance: 9 {}\n", long)), ["0", "too large"]);
+    }
+}
@@ -1,6 +1,18 @@
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::random::Rng;
+use aoc_core::Options;
 use std::cmp::Ordering;
-use std::io::stdin;
-use std::time::Instant;
+use std::io::{BufRead, Write};
+
+aoc_core::solution! {
+    year: 2023,
+    day: 7,
+    parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+    stream: stream_input,
+    generate: generate_input,
+}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 enum Card {
@@ -38,6 +50,24 @@ impl Card {
         }
     }
 
+    fn to_char(self) -> char {
+        match self {
+            Card::N2 => '2',
+            Card::N3 => '3',
+            Card::N4 => '4',
+            Card::N5 => '5',
+            Card::N6 => '6',
+            Card::N7 => '7',
+            Card::N8 => '8',
+            Card::N9 => '9',
+            Card::T => 'T',
+            Card::J => 'J',
+            Card::Q => 'Q',
+            Card::K => 'K',
+            Card::A => 'A',
+        }
+    }
+
     fn cards() -> Vec<Card> {
         vec![
             Card::N2,
@@ -84,6 +114,10 @@ struct Hand {
 }
 
 impl Hand {
+    fn label(&self) -> String {
+        self.cards.iter().map(|card| card.to_char()).collect()
+    }
+
     fn compare_part_1(&self, other: &Hand) -> Ordering {
         match self.hand_type.cmp(&other.hand_type) {
             Ordering::Less => Ordering::Less,
@@ -125,18 +159,16 @@ impl Hand {
     }
 }
 
-fn parse_input() -> Vec<(Hand, usize)> {
-    stdin()
-        .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
-            let bid = parts[1].parse().unwrap();
-            let hand = parse_hand(parts[0]);
+fn parse_input(input: &str) -> Vec<(Hand, usize)> {
+    input.lines().map(parse_line).collect::<Vec<_>>()
+}
 
-            (hand, bid)
-        })
-        .collect::<Vec<_>>()
+fn parse_line(line: &str) -> (Hand, usize) {
+    let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
+    let bid = parts[1].parse().unwrap();
+    let hand = parse_hand(parts[0]);
+
+    (hand, bid)
 }
 
 fn parse_hand(string: &str) -> Hand {
@@ -172,7 +204,7 @@ fn parse_hand(string: &str) -> Hand {
     hand
 }
 
-fn get_hand_type(card_counts: &Vec<(usize, Card)>) -> HandType {
+fn get_hand_type(card_counts: &[(usize, Card)]) -> HandType {
     match card_counts[0].0 {
         5 => HandType::FiveOfAKind,
         4 => HandType::FourOfAKind,
@@ -194,8 +226,8 @@ fn get_hand_type(card_counts: &Vec<(usize, Card)>) -> HandType {
     }
 }
 
-fn get_hand_type_p2(card_counts: &Vec<(usize, Card)>) -> HandType {
-    let mut card_counts = card_counts.clone();
+fn get_hand_type_p2(card_counts: &[(usize, Card)]) -> HandType {
+    let mut card_counts = card_counts.to_vec();
     let j_pos = card_counts.iter().position(|&c| c.1 == Card::J).unwrap();
     let j_count = card_counts[j_pos].0;
     card_counts.remove(j_pos);
@@ -221,38 +253,106 @@ fn get_hand_type_p2(card_counts: &Vec<(usize, Card)>) -> HandType {
     }
 }
 
-fn solve_part_1(hands: &Vec<(Hand, usize)>) {
-    let mut hands = hands.clone();
+/// Prints the hands in rank order, with the hand type that was used to sort them.
+fn explain_ranking(part: &str, hands: &[(Hand, usize)], hand_type: fn(&Hand) -> HandType) {
+    println!("{}:", part);
+    for (position, (hand, bid)) in hands.iter().enumerate() {
+        println!(
+            "  rank {}: {} {:?}, bid {} => {}",
+            position + 1,
+            hand.label(),
+            hand_type(hand),
+            bid,
+            (position + 1) * bid
+        );
+    }
+}
+
+fn solve_part_1(hands: &[(Hand, usize)], options: &Options) -> usize {
+    let mut hands = hands.to_vec();
     hands.sort_by(|a, b| a.0.compare_part_1(&b.0));
 
-    let result: usize = hands
+    if options.explain {
+        explain_ranking("part 1", &hands, |hand| hand.hand_type);
+    }
+
+    hands
         .iter()
         .enumerate()
         .map(|(position, hand)| (position + 1) * hand.1)
-        .sum();
-
-    println!("{}", result);
+        .sum()
 }
 
-fn solve_part_2(hands: &Vec<(Hand, usize)>) {
-    let mut hands = hands.clone();
+fn solve_part_2(hands: &[(Hand, usize)], options: &Options) -> usize {
+    let mut hands = hands.to_vec();
     hands.sort_by(|a, b| a.0.compare_part_2(&b.0));
 
-    let result: usize = hands
+    if options.explain {
+        explain_ranking("part 2", &hands, |hand| hand.hand_type_alt);
+    }
+
+    hands
         .iter()
         .enumerate()
         .map(|(position, hand)| (position + 1) * hand.1)
-        .sum();
+        .sum()
+}
+
+/// Bids of all the occurrences of one distinct hand.
+///
+/// `weighted_bids` sums each bid times the number of identical hands read before it: identical
+/// hands then get consecutive ranks in input order, as with the stable sort of the other mode.
+#[derive(Debug, Clone, Copy, Default)]
+struct HandBids {
+    count: u128,
+    bids: u128,
+    weighted_bids: u128,
+}
 
-    println!("{}", result);
+/// Position of a hand among the 13^5 possible ones.
+fn hand_index(hand: &Hand) -> usize {
+    hand.cards
+        .iter()
+        .fold(0, |index, card| index * 13 + *card as usize)
 }
 
-fn main() {
-    let start_time = Instant::now();
-    let data = parse_input();
+/// Solves both parts with a fixed size table of bids per distinct hand: ranking only needs to
+/// sort the distinct hands, whatever the number of lines.
+fn stream_input(reader: &mut dyn BufRead, _options: &Options) -> [String; 2] {
+    let mut table = vec![HandBids::default(); 13_usize.pow(5)];
+    let mut hands = Vec::new();
+
+    for_each_line(reader, |line| {
+        let (hand, bid) = parse_line(line);
+        let entry = &mut table[hand_index(&hand)];
+        if entry.count == 0 {
+            hands.push(hand);
+        }
+        entry.weighted_bids += entry.count * bid as u128;
+        entry.count += 1;
+        entry.bids += bid as u128;
+    });
 
-    solve_part_1(&data);
-    solve_part_2(&data);
+    [Hand::compare_part_1, Hand::compare_part_2].map(|compare| {
+        hands.sort_by(compare);
 
-    eprintln!("{} µs", (Instant::now() - start_time).as_micros());
+        let mut rank = 1;
+        let mut winnings = 0;
+        for hand in &hands {
+            let entry = table[hand_index(hand)];
+            winnings += rank * entry.bids + entry.weighted_bids;
+            rank += entry.count;
+        }
+        winnings.to_string()
+    })
+}
+
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    let cards = Card::cards();
+    generate_lines(rng, size, out, |rng, line| {
+        for _ in 0..5 {
+            line.push(rng.pick(&cards).to_char() as u8);
+        }
+        line.extend_from_slice(format!(" {}", rng.range(1, 1000)).as_bytes());
+    });
 }
@@ -0,0 +1,87 @@
+use aoc_core::arith::{self, Number};
+use aoc_core::graph::Graph;
+use aoc_core::math::lcm;
+use aoc_core::numeric::BigInt;
+use aoc_core::parse::{blocks, ParseError, Pattern};
+use aoc_core::Options;
+
+aoc_core::solution! {
+    year: 2023,
+    day: 8,
+    try_parse: parse_input,
+    part_1: solve_part_1,
+    part_2: solve_part_2::<BigInt>,
+}
+
+/// The network, where the edges of each node lead to its left then right child.
+struct Statement {
+    instructions: Vec<usize>,
+    nodes: Graph,
+}
+
+const NODE: Pattern = Pattern::new("{} = ({}, {})");
+
+fn parse_input(input: &str) -> Result<Statement, ParseError> {
+    let mut blocks = blocks(input);
+    let instructions = blocks.next().ok_or(ParseError::missing("instructions"))?;
+    let instructions = instructions.parse(|line| {
+        line.chars()
+            .map(|c| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(ParseError::invalid("L or R", &c.to_string())),
+            })
+            .collect()
+    })?;
+
+    let mut nodes = Graph::directed();
+    for (node_label, child_left_label, child_right_label) in blocks
+        .next()
+        .ok_or(ParseError::missing("nodes"))?
+        .lines(|line| NODE.parse::<(&str, &str, &str)>(line))?
+    {
+        let node = nodes.node(node_label);
+        let child_left = nodes.node(child_left_label);
+        let child_right = nodes.node(child_right_label);
+        nodes.add_edge(node, child_left);
+        nodes.add_edge(node, child_right);
+    }
+
+    Ok(Statement { instructions, nodes })
+}
+
+fn reach_z(statement: &Statement, start_node: usize) -> usize {
+    let mut current_node = start_node;
+    let mut current_instruction_index = 0;
+    let mut path_length = 0;
+
+    while !statement.nodes.label(current_node).ends_with('Z') {
+        current_node = statement.nodes.neighbours(current_node)[statement.instructions[current_instruction_index]];
+        current_instruction_index = (current_instruction_index + 1) % statement.instructions.len();
+        path_length += 1;
+    }
+
+    path_length
+}
+
+fn solve_part_1(statement: &Statement, _options: &Options) -> usize {
+    reach_z(statement, statement.nodes.index("AAA").unwrap())
+}
+
+fn solve_part_2<T: Number + TryFrom<usize>>(statement: &Statement, _options: &Options) -> T {
+    let all_path_length = (0..statement.nodes.len())
+        .filter_map(|node| {
+            if statement.nodes.label(node).ends_with('A') {
+                Some(reach_z(statement, node))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    all_path_length
+        .iter()
+        .map(|&path_length| arith::convert(path_length))
+        .reduce(lcm)
+        .unwrap()
+}
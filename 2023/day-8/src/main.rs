@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::main(&aoc_2023_day_8::SOLUTION);
+}
@@ -0,0 +1,155 @@
+use aoc_core::arith::{self, Number};
+use aoc_core::input::{for_each_line, generate_lines};
+use aoc_core::random::Rng;
+use aoc_core::Options;
+use std::io::{BufRead, Write};
+
+aoc_core::solution! {
+    year: 2023,
+    day: 9,
+    parse: parse_input,
+    part_1: solve_part_1::<i64>,
+    part_2: solve_part_2::<i64>,
+    stream: stream_input,
+    generate: generate_input,
+}
+
+fn parse_input(input: &str) -> Vec<Vec<i64>> {
+    input.lines().map(parse_history).collect()
+}
+
+fn parse_history(line: &str) -> Vec<i64> {
+    line.split_ascii_whitespace()
+        .map(|parts| parts.parse().unwrap())
+        .collect()
+}
+
+/// Builds the successive difference sequences of a history, down to the all-zero one. The
+/// differences of high degree histories grow fast, hence the choice of their type.
+fn difference_sequences<T: Number + TryFrom<i64>>(history: &[i64]) -> Vec<Vec<T>> {
+    let mut sequences = vec![history.iter().map(|&v| arith::convert(v)).collect::<Vec<T>>()];
+
+    // Down pass
+    loop {
+        // Stop condition
+        if sequences
+            .last()
+            .unwrap()
+            .iter()
+            .find(|&v| *v != T::zero())
+            .is_none()
+        {
+            break;
+        }
+
+        let mut sequence_new = Vec::new();
+        let sequence_last = sequences.last().unwrap();
+        for (a, b) in sequence_last[0..sequence_last.len() - 1]
+            .iter()
+            .zip(sequence_last[1..].iter())
+        {
+            sequence_new.push(arith::sub(b.clone(), a.clone()));
+        }
+
+        sequences.push(sequence_new)
+    }
+
+    sequences
+}
+
+fn extrapolate_next<T: Number>(sequences: &[Vec<T>]) -> T {
+    // Sum the lat elements
+    arith::sum(sequences.iter().map(|sequence| sequence.last().unwrap().clone()))
+}
+
+fn extrapolate_previous<T: Number>(sequences: &[Vec<T>]) -> T {
+    let mut v = T::zero();
+    for i in (0..sequences.len()).rev() {
+        v = arith::sub(sequences[i][0].clone(), v);
+    }
+    v
+}
+
+fn explain_extrapolation<T: Number>(history_index: usize, history: &[i64], depth: usize, value: &T) {
+    println!(
+        "  history {}: {} ({} difference levels) => {}",
+        history_index + 1,
+        history
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        depth - 1,
+        value
+    );
+}
+
+fn solve_part_1<T: Number + TryFrom<i64>>(statement: &[Vec<i64>], options: &Options) -> T {
+    if options.explain {
+        println!("part 1:");
+    }
+
+    arith::sum(statement
+        .iter()
+        .enumerate()
+        .map(|(i, history)| {
+            let sequences = difference_sequences(history);
+
+            let v = extrapolate_next(&sequences);
+
+            if options.explain {
+                explain_extrapolation(i, history, sequences.len(), &v);
+            }
+            v
+        }))
+}
+
+fn solve_part_2<T: Number + TryFrom<i64>>(statement: &[Vec<i64>], options: &Options) -> T {
+    if options.explain {
+        println!("part 2:");
+    }
+
+    arith::sum(statement
+        .iter()
+        .enumerate()
+        .map(|(i, history)| {
+            let sequences = difference_sequences(history);
+
+            let v = extrapolate_previous(&sequences);
+
+            if options.explain {
+                explain_extrapolation(i, history, sequences.len(), &v);
+            }
+            v
+        }))
+}
+
+/// Extrapolates both ways history by history.
+fn stream_input(reader: &mut dyn BufRead, _options: &Options) -> [String; 2] {
+    let mut sums = [0_i128, 0_i128];
+
+    for_each_line(reader, |line| {
+        let sequences = difference_sequences(&parse_history(line));
+        sums[0] = arith::add(sums[0], extrapolate_next(&sequences));
+        sums[1] = arith::add(sums[1], extrapolate_previous(&sequences));
+    });
+
+    sums.map(|sum| sum.to_string())
+}
+
+/// Histories of 21 values of random polynomials of degree at most 4.
+fn generate_input(rng: &mut Rng, size: u64, out: &mut dyn Write) {
+    generate_lines(rng, size, out, |rng, line| {
+        let coefficients = (0..rng.range(1, 5)).map(|_| rng.range(-9, 9)).collect::<Vec<_>>();
+        let values = (0..21_i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |value, coefficient| value * x + coefficient)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        line.extend_from_slice(values.join(" ").as_bytes());
+    });
+}
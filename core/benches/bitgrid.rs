@@ -0,0 +1,186 @@
+//! Byte grids against bit grids, on the operations days 13, 14 and 16 are made of.
+//!
+//! `cargo bench -p aoc-core --bench bitgrid`
+
+use aoc_core::bitgrid::BitGrid;
+use aoc_core::random::Rng;
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let mut rng = Rng::new(2023);
+
+    compare(
+        "day 13: mirror differences, 10k 17x17 patterns",
+        &(0..10_000).map(|_| random_text(&mut rng, 17, 17, "#.")).collect::<Vec<_>>(),
+        |patterns| {
+            let patterns = patterns.iter().map(|pattern| to_bytes(pattern)).collect::<Vec<_>>();
+            move || patterns.iter().map(|pattern| byte_mirror_differences(pattern)).sum::<usize>()
+        },
+        |patterns| {
+            let patterns = patterns.iter().map(|pattern| BitGrid::<u32>::from_text(pattern, b'#')).collect::<Vec<_>>();
+            move || patterns.iter().map(bit_mirror_differences).sum::<usize>()
+        },
+    );
+
+    compare(
+        "day 14: tilting north, 100x100 platform",
+        &random_text(&mut rng, 100, 100, "O.#.."),
+        |platform| {
+            let platform = to_bytes(platform);
+            move || byte_roll_north(platform.clone())
+        },
+        |platform| {
+            let round_rocks = BitGrid::<u128>::from_text(platform, b'O');
+            let cube_rocks = BitGrid::<u128>::from_text(platform, b'#');
+            move || bit_roll_north(&round_rocks, &cube_rocks)
+        },
+    );
+
+    let beams = (0..1_000_000)
+        .map(|_| (rng.below(110) as usize, rng.below(110) as usize, rng.below(4) as usize))
+        .collect::<Vec<_>>();
+    compare(
+        "day 16: 1M beam steps on 110x110",
+        &beams,
+        |beams| {
+            let beams = beams.clone();
+            move || {
+                let mut energized = vec![false; 110 * 110];
+                let mut visited = HashSet::new();
+                for &(x, y, direction) in &beams {
+                    if visited.insert((x, y, direction)) {
+                        energized[y * 110 + x] = true;
+                    }
+                }
+                energized.iter().filter(|&&cell| cell).count()
+            }
+        },
+        |beams| {
+            let beams = beams.clone();
+            move || {
+                let mut visited: [BitGrid; 4] = std::array::from_fn(|_| BitGrid::new(110, 110));
+                for &(x, y, direction) in &beams {
+                    visited[direction].insert(x, y);
+                }
+                let [up, right, down, left] = &visited;
+                (&(up | right) | &(down | left)).count()
+            }
+        },
+    );
+}
+
+/// Times both versions, built from the same data, and prints the speedup of the bit grid.
+fn compare<D, T: Eq + std::fmt::Debug, B: FnMut() -> T, G: FnMut() -> T>(
+    name: &str,
+    data: &D,
+    bytes: impl FnOnce(&D) -> B,
+    bits: impl FnOnce(&D) -> G,
+) {
+    let (mut bytes, mut bits) = (bytes(data), bits(data));
+    assert_eq!(bytes(), bits(), "{}: both versions must agree", name);
+
+    let bytes_time = time(&mut bytes);
+    let bits_time = time(&mut bits);
+    println!(
+        "{:<48} bytes {:>10.3} ms   bits {:>10.3} ms   x{:.1}",
+        name,
+        bytes_time.as_secs_f64() * 1000.0,
+        bits_time.as_secs_f64() * 1000.0,
+        bytes_time.as_secs_f64() / bits_time.as_secs_f64()
+    );
+}
+
+/// Best time out of enough runs to last about half a second.
+fn time<T>(f: &mut impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_millis(500) {
+        let run_start = Instant::now();
+        black_box(f());
+        best = best.min(run_start.elapsed());
+    }
+    best
+}
+
+fn random_text(rng: &mut Rng, width: usize, height: usize, cells: &str) -> String {
+    let cells = cells.as_bytes();
+    let mut text = String::new();
+    for _ in 0..height {
+        text.extend((0..width).map(|_| *rng.pick(cells) as char));
+        text.push('\n');
+    }
+    text
+}
+
+fn to_bytes(text: &str) -> Vec<Vec<u8>> {
+    text.lines().map(|line| line.as_bytes().to_vec()).collect()
+}
+
+/// Number of differing cells across every horizontal mirror line, cell by cell.
+fn byte_mirror_differences(pattern: &[Vec<u8>]) -> usize {
+    let mut differences = 0;
+    for split in 1..pattern.len() {
+        for y in 0..split.min(pattern.len() - split) {
+            differences += pattern[split - y - 1]
+                .iter()
+                .zip(&pattern[split + y])
+                .filter(|(a, b)| a != b)
+                .count();
+        }
+    }
+    differences
+}
+
+/// Number of differing cells across every horizontal mirror line, row by row.
+fn bit_mirror_differences(pattern: &BitGrid<u32>) -> usize {
+    let mut differences = 0;
+    for split in 1..pattern.height() {
+        for y in 0..split.min(pattern.height() - split) {
+            differences += pattern.row_xor(split - y - 1, split + y).count_ones() as usize;
+        }
+    }
+    differences
+}
+
+fn byte_roll_north(mut board: Vec<Vec<u8>>) -> Vec<(usize, usize)> {
+    loop {
+        let mut move_occured = false;
+        for y in 1..board.len() {
+            for x in 0..board[0].len() {
+                if board[y][x] == b'O' && board[y - 1][x] == b'.' {
+                    board[y][x] = b'.';
+                    board[y - 1][x] = b'O';
+                    move_occured = true;
+                }
+            }
+        }
+        if !move_occured {
+            break;
+        }
+    }
+
+    let mut rocks = Vec::new();
+    for (y, row) in board.iter().enumerate() {
+        for (x, &cell) in row.iter().enumerate() {
+            if cell == b'O' {
+                rocks.push((x, y));
+            }
+        }
+    }
+    rocks
+}
+
+fn bit_roll_north(round_rocks: &BitGrid<u128>, cube_rocks: &BitGrid<u128>) -> Vec<(usize, usize)> {
+    let mut round_rocks = round_rocks.clone();
+    loop {
+        let free = (&round_rocks | cube_rocks).complement();
+        let moving = &round_rocks & &free.shift_south(1);
+        if moving.is_empty() {
+            break;
+        }
+        round_rocks = &(&round_rocks ^ &moving) | &moving.shift_north(1);
+    }
+    round_rocks.iter().collect()
+}
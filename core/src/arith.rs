@@ -0,0 +1,142 @@
+//! Arithmetic for the values answers are accumulated in.
+//!
+//! By default these functions are the plain operators, which silently wrap in release builds.
+//! With the `checked` feature, every operation is checked and an overflow stops the part with an
+//! [`Overflow`] report naming the year, day and part, instead of giving a wrong answer.
+
+use std::any::type_name;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::panic;
+use std::sync::Once;
+
+/// Integer types an answer can be computed in, from the primitive ones to
+/// [`BigInt`](crate::numeric::BigInt). A day picks the type when registering its parts, e.g.
+/// `part_1: solve_part_1::<u128>`.
+pub trait Number:
+    Clone
+    + Debug
+    + Display
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// Payload of the panic raised when a checked operation overflows.
+#[derive(Debug)]
+pub struct Overflow {
+    pub operation: &'static str,
+    pub type_name: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} overflowed {}", self.operation, self.type_name)
+    }
+}
+
+fn overflow<T>(operation: &'static str) -> ! {
+    panic::panic_any(Overflow {
+        operation,
+        type_name: type_name::<T>(),
+    })
+}
+
+/// Keeps the default panic message out of the way for overflows, which the runner reports itself.
+pub(crate) fn install_overflow_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if info.payload().downcast_ref::<Overflow>().is_none() {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+pub fn add<T: Number>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_add(b).unwrap_or_else(|| overflow::<T>("addition"))
+    } else {
+        a + b
+    }
+}
+
+pub fn sub<T: Number>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_sub(b).unwrap_or_else(|| overflow::<T>("subtraction"))
+    } else {
+        a - b
+    }
+}
+
+pub fn mul<T: Number>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_mul(b).unwrap_or_else(|| overflow::<T>("multiplication"))
+    } else {
+        a * b
+    }
+}
+
+pub fn pow<T: Number>(base: T, exponent: u32) -> T {
+    let mut result = T::one();
+    for _ in 0..exponent {
+        result = mul(result, base.clone());
+    }
+    result
+}
+
+pub fn sum<T: Number>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), add)
+}
+
+pub fn product<T: Number>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), mul)
+}
+
+/// Converts a value into the answer type, which it must fit in.
+pub fn convert<T: Number + TryFrom<V>, V>(value: V) -> T {
+    T::try_from(value).unwrap_or_else(|_| overflow::<T>("conversion"))
+}
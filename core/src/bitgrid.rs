@@ -0,0 +1,293 @@
+//! Grids of booleans packed one row per machine word.
+//!
+//! Bit `x` of row `y` is the cell at column `x`, so that whole rows are compared, counted and
+//! moved with single word operations:
+//!
+//! ```
+//! use aoc_core::bitgrid::BitGrid;
+//!
+//! let rocks = BitGrid::<u64>::from_text("O.#\n.O.\nO..", b'O');
+//! assert_eq!(rocks.count(), 3);
+//! assert_eq!(rocks.shift_north(1).count(), 2);
+//! assert_eq!(rocks.transpose().to_text('O', '.'), "O.O\n.O.\n...\n");
+//! assert_eq!((rocks.row(0) ^ rocks.row(2)).count_ones(), 0);
+//! ```
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+/// Unsigned integer holding one row of a [`BitGrid`].
+pub trait Word:
+    Copy
+    + Debug
+    + Eq
+    + Hash
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+{
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u32, u64, u128);
+
+/// Grid of `width` columns, at most the number of bits of `W`, and any number of rows. Bits
+/// beyond the width are always zero.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BitGrid<W: Word = u128> {
+    rows: Vec<W>,
+    width: usize,
+}
+
+impl<W: Word> BitGrid<W> {
+    pub fn new(width: usize, height: usize) -> BitGrid<W> {
+        assert!(width <= W::BITS, "{} columns do not fit in a {} bits row", width, W::BITS);
+        BitGrid {
+            rows: vec![W::ZERO; height],
+            width,
+        }
+    }
+
+    /// Grid of the cells of a text equal to `set`, one line per row, as wide as the longest line.
+    /// The cells past the end of a shorter line are not set.
+    pub fn from_text(text: &str, set: u8) -> BitGrid<W> {
+        let lines = text.lines().collect::<Vec<_>>();
+        let mut grid = BitGrid::new(lines.iter().map(|line| line.len()).max().unwrap_or(0), lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, &c) in line.as_bytes().iter().enumerate() {
+                grid.set(x, y, c == set);
+            }
+        }
+        grid
+    }
+
+    /// Text of the grid, one line per row, for debugging or explanations.
+    pub fn to_text(&self, set: char, unset: char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width {
+                text.push(if self.get(x, y) { set } else { unset });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.rows[y] & (W::ONE << x) != W::ZERO
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(x < self.width);
+        if value {
+            self.rows[y] = self.rows[y] | (W::ONE << x);
+        } else {
+            self.rows[y] = self.rows[y] & !(W::ONE << x);
+        }
+    }
+
+    /// Sets a cell, returning whether it was unset before.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        let was_set = self.get(x, y);
+        self.set(x, y, true);
+        !was_set
+    }
+
+    pub fn row(&self, y: usize) -> W {
+        self.rows[y]
+    }
+
+    pub fn rows(&self) -> &[W] {
+        &self.rows
+    }
+
+    /// Replaces a row, dropping the bits beyond the width.
+    pub fn set_row(&mut self, y: usize, row: W) {
+        self.rows[y] = row & self.mask();
+    }
+
+    /// Number of set cells.
+    pub fn count(&self) -> usize {
+        self.rows.iter().map(|row| row.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == W::ZERO)
+    }
+
+    /// Coordinates of the set cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, &row)| {
+            let mut row = row;
+            std::iter::from_fn(move || {
+                (row != W::ZERO).then(|| {
+                    let x = row.trailing_zeros() as usize;
+                    row = row & !(W::ONE << x);
+                    (x, y)
+                })
+            })
+        })
+    }
+
+    /// Cells that differ between two rows.
+    pub fn row_xor(&self, first: usize, second: usize) -> W {
+        self.rows[first] ^ self.rows[second]
+    }
+
+    /// Every cell moved `n` rows up, the bottom rows becoming unset.
+    pub fn shift_north(&self, n: usize) -> BitGrid<W> {
+        let mut rows = self.rows.iter().skip(n).copied().collect::<Vec<_>>();
+        rows.resize(self.height(), W::ZERO);
+        BitGrid { rows, width: self.width }
+    }
+
+    /// Every cell moved `n` rows down, the top rows becoming unset.
+    pub fn shift_south(&self, n: usize) -> BitGrid<W> {
+        let n = n.min(self.height());
+        let mut rows = vec![W::ZERO; n];
+        rows.extend_from_slice(&self.rows[..self.height() - n]);
+        BitGrid { rows, width: self.width }
+    }
+
+    /// Every cell moved `n` columns left.
+    pub fn shift_west(&self, n: usize) -> BitGrid<W> {
+        self.map_rows(|row| if n < W::BITS { row >> n } else { W::ZERO })
+    }
+
+    /// Every cell moved `n` columns right, the cells moved past the width being dropped.
+    pub fn shift_east(&self, n: usize) -> BitGrid<W> {
+        self.map_rows(|row| if n < W::BITS { row << n } else { W::ZERO })
+    }
+
+    /// Grid whose rows are the columns of this one. The height must fit in a row.
+    pub fn transpose(&self) -> BitGrid<W> {
+        let mut transposed = BitGrid::new(self.height(), self.width);
+        for (x, y) in self.iter() {
+            transposed.rows[x] = transposed.rows[x] | (W::ONE << y);
+        }
+        transposed
+    }
+
+    /// Every cell flipped.
+    pub fn complement(&self) -> BitGrid<W> {
+        self.map_rows(|row| !row)
+    }
+
+    fn map_rows(&self, f: impl Fn(W) -> W) -> BitGrid<W> {
+        let mask = self.mask();
+        BitGrid {
+            rows: self.rows.iter().map(|&row| f(row) & mask).collect(),
+            width: self.width,
+        }
+    }
+
+    fn zip_rows(&self, other: &BitGrid<W>, f: impl Fn(W, W) -> W) -> BitGrid<W> {
+        assert_eq!((self.width, self.height()), (other.width, other.height()), "grids of different sizes");
+        BitGrid {
+            rows: self.rows.iter().zip(&other.rows).map(|(&a, &b)| f(a, b)).collect(),
+            width: self.width,
+        }
+    }
+
+    fn mask(&self) -> W {
+        if self.width == W::BITS {
+            !W::ZERO
+        } else {
+            !(!W::ZERO << self.width)
+        }
+    }
+}
+
+/// Union
+impl<W: Word> BitOr for &BitGrid<W> {
+    type Output = BitGrid<W>;
+
+    fn bitor(self, other: &BitGrid<W>) -> BitGrid<W> {
+        self.zip_rows(other, |a, b| a | b)
+    }
+}
+
+/// Intersection
+impl<W: Word> BitAnd for &BitGrid<W> {
+    type Output = BitGrid<W>;
+
+    fn bitand(self, other: &BitGrid<W>) -> BitGrid<W> {
+        self.zip_rows(other, |a, b| a & b)
+    }
+}
+
+/// Symmetric difference
+impl<W: Word> BitXor for &BitGrid<W> {
+    type Output = BitGrid<W>;
+
+    fn bitxor(self, other: &BitGrid<W>) -> BitGrid<W> {
+        self.zip_rows(other, |a, b| a ^ b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_text() {
+        let grid = BitGrid::<u32>::from_text("", b'#');
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert!(grid.is_empty());
+        assert_eq!(grid.to_text('#', '.'), "");
+        assert_eq!(grid.transpose(), grid);
+    }
+
+    #[test]
+    fn uneven_lines() {
+        let grid = BitGrid::<u32>::from_text("#.\n..##\n\n#", b'#');
+        assert_eq!((grid.width(), grid.height()), (4, 4));
+        assert_eq!(grid.to_text('#', '.'), "#...\n..##\n....\n#...\n");
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [(0, 0), (2, 1), (3, 1), (0, 3)]);
+        assert_eq!(grid.transpose().to_text('#', '.'), "#..#\n....\n.#..\n.#..\n");
+        assert_eq!(grid.shift_east(1).to_text('#', '.'), ".#..\n...#\n....\n.#..\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "33 columns do not fit in a 32 bits row")]
+    fn too_wide() {
+        BitGrid::<u32>::from_text(&format!("#\n{}", ".".repeat(33)), b'#');
+    }
+}
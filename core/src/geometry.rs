@@ -0,0 +1,520 @@
+//! Points, boxes, segments and rays on the integer lattice, and polygons of the plane.
+//!
+//! Coordinates are `i64`. Products of coordinates are computed in `i128`, and the crossing of two
+//! lines is exact, as a [`Rational`], so that far away hailstones do not lose precision:
+//!
+//! ```
+//! use aoc_core::geometry::{Aabb3, Point2, Point3, Ray2};
+//! use aoc_core::numeric::Rational;
+//!
+//! let brick = Aabb3::new(Point3::new(1, 0, 1), Point3::new(1, 2, 1));
+//! let below = Aabb3::new(Point3::new(0, 0, 0), Point3::new(2, 0, 0));
+//! assert!(!brick.overlaps(&below));
+//! assert!(brick.translate(Point3::new(0, 0, -1)).overlaps(&below));
+//!
+//! let first = Ray2::new(Point2::new(19, 13), Point2::new(-2, 1));
+//! let second = Ray2::new(Point2::new(18, 19), Point2::new(-1, -1));
+//! let crossing = first.crossing(&second).unwrap();
+//! assert_eq!(crossing.point, [Rational::new(43, 3), Rational::new(46, 3)]);
+//! assert!(crossing.is_ahead());
+//! ```
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::arith;
+use crate::math::gcd;
+use crate::numeric::Rational;
+
+/// Point of the plane, also used as a vector.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Z component of the cross product, positive when `other` is counter-clockwise from `self`
+    /// with the y axis pointing up.
+    pub fn cross(&self, other: Point2) -> i128 {
+        self.x as i128 * other.y as i128 - self.y as i128 * other.x as i128
+    }
+
+    pub fn dot(&self, other: Point2) -> i128 {
+        self.x as i128 * other.x as i128 + self.y as i128 * other.y as i128
+    }
+}
+
+/// Point of the space, also used as a vector.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The point seen from above, dropping `z`.
+    pub fn xy(&self) -> Point2 {
+        Point2::new(self.x, self.y)
+    }
+
+    pub fn dot(&self, other: Point3) -> i128 {
+        dot(self.wide(), other.wide())
+    }
+
+    pub fn cross(&self, other: Point3) -> [i128; 3] {
+        cross(self.wide(), other.wide())
+    }
+
+    fn wide(&self) -> [i128; 3] {
+        [self.x as i128, self.y as i128, self.z as i128]
+    }
+
+    fn min_per_axis(&self, other: Point3) -> Point3 {
+        Point3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    fn max_per_axis(&self, other: Point3) -> Point3 {
+        Point3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+}
+
+fn dot(a: [i128; 3], b: [i128; 3]) -> i128 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+macro_rules! impl_vector_ops {
+    ($point:ident: $($field:ident),+) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: arith::add(self.$field, other.$field)),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: arith::sub(self.$field, other.$field)),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: i64) -> $point {
+                $point { $($field: arith::mul(self.$field, factor)),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Point2: x, y);
+impl_vector_ops!(Point3: x, y, z);
+
+/// Axis-aligned box of lattice cells, both corners included, as bricks and voxels are given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Aabb3 {
+    /// Box between two opposite corners, in any order.
+    pub fn new(a: Point3, b: Point3) -> Aabb3 {
+        Aabb3 {
+            min: a.min_per_axis(b),
+            max: a.max_per_axis(b),
+        }
+    }
+
+    /// Smallest box containing every point, `None` when there is none.
+    pub fn bounding(points: impl IntoIterator<Item = Point3>) -> Option<Aabb3> {
+        points.into_iter().fold(None, |bounds: Option<Aabb3>, point| {
+            Some(bounds.map_or(Aabb3::new(point, point), |bounds| bounds.union(&Aabb3::new(point, point))))
+        })
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        self.min.min_per_axis(point) == self.min && self.max.max_per_axis(point) == self.max
+    }
+
+    /// Whether both boxes share at least one cell.
+    pub fn overlaps(&self, other: &Aabb3) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the boxes seen from above share at least one cell, as bricks falling along `z` do.
+    pub fn overlaps_xy(&self, other: &Aabb3) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    /// Cells shared by both boxes.
+    pub fn intersection(&self, other: &Aabb3) -> Option<Aabb3> {
+        let (min, max) = (self.min.max_per_axis(other.min), self.max.min_per_axis(other.max));
+        (min.min_per_axis(max) == min).then_some(Aabb3 { min, max })
+    }
+
+    /// Smallest box containing both.
+    pub fn union(&self, other: &Aabb3) -> Aabb3 {
+        Aabb3 {
+            min: self.min.min_per_axis(other.min),
+            max: self.max.max_per_axis(other.max),
+        }
+    }
+
+    pub fn translate(&self, offset: Point3) -> Aabb3 {
+        Aabb3 {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// Number of cells along each axis.
+    pub fn size(&self) -> Point3 {
+        self.max - self.min + Point3::new(1, 1, 1)
+    }
+
+    /// Number of cells.
+    pub fn volume(&self) -> i64 {
+        let size = self.size();
+        arith::product([size.x, size.y, size.z])
+    }
+}
+
+/// Where two lines cross: the parameter of the crossing along each of them, and the point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crossing<const N: usize> {
+    pub t: Rational,
+    pub u: Rational,
+    pub point: [Rational; N],
+}
+
+impl<const N: usize> Crossing<N> {
+    /// Whether the crossing is ahead of both ray origins, origins included.
+    pub fn is_ahead(&self) -> bool {
+        let zero = Rational::from(0);
+        self.t >= zero && self.u >= zero
+    }
+
+    /// Whether the crossing lies on both segments, ends included.
+    pub fn is_within(&self) -> bool {
+        let range = Rational::from(0)..=Rational::from(1);
+        range.contains(&self.t) && range.contains(&self.u)
+    }
+}
+
+/// Half-line from `origin` along `direction`, or position over time of something moving at a
+/// constant velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray2 {
+    pub origin: Point2,
+    pub direction: Point2,
+}
+
+impl Ray2 {
+    pub fn new(origin: Point2, direction: Point2) -> Ray2 {
+        Ray2 { origin, direction }
+    }
+
+    pub fn at(&self, t: i64) -> Point2 {
+        self.origin + self.direction * t
+    }
+
+    /// Crossing of the lines carrying both rays, `None` when they are parallel. The crossing may be
+    /// behind either origin, see [`Crossing::is_ahead`].
+    pub fn crossing(&self, other: &Ray2) -> Option<Crossing<2>> {
+        let denominator = self.direction.cross(other.direction);
+        if denominator == 0 {
+            return None;
+        }
+
+        let offset = other.origin - self.origin;
+        let t = Rational::new(offset.cross(other.direction), denominator);
+        let u = Rational::new(offset.cross(self.direction), denominator);
+        let point = [
+            Rational::from(self.origin.x) + &t * &Rational::from(self.direction.x),
+            Rational::from(self.origin.y) + &t * &Rational::from(self.direction.y),
+        ];
+        Some(Crossing { t, u, point })
+    }
+}
+
+/// Half-line from `origin` along `direction`, or position over time of something moving at a
+/// constant velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray3 {
+    pub origin: Point3,
+    pub direction: Point3,
+}
+
+impl Ray3 {
+    pub fn new(origin: Point3, direction: Point3) -> Ray3 {
+        Ray3 { origin, direction }
+    }
+
+    pub fn at(&self, t: i64) -> Point3 {
+        self.origin + self.direction * t
+    }
+
+    /// The ray seen from above, dropping `z`.
+    pub fn xy(&self) -> Ray2 {
+        Ray2::new(self.origin.xy(), self.direction.xy())
+    }
+
+    /// Crossing of the lines carrying both rays, `None` when they are parallel or skew. The
+    /// crossing may be behind either origin, see [`Crossing::is_ahead`].
+    ///
+    /// ```
+    /// use aoc_core::geometry::{Point3, Ray3};
+    /// use aoc_core::numeric::Rational;
+    ///
+    /// let first = Ray3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+    /// let second = Ray3::new(Point3::new(4, 0, 0), Point3::new(-1, 1, 1));
+    /// let crossing = first.crossing(&second).unwrap();
+    /// assert_eq!((crossing.t, crossing.u), (Rational::from(2), Rational::from(2)));
+    ///
+    /// let skew = Ray3::new(Point3::new(4, 0, 1), Point3::new(-1, 1, 1));
+    /// assert_eq!(first.crossing(&skew), None);
+    /// ```
+    pub fn crossing(&self, other: &Ray3) -> Option<Crossing<3>> {
+        let normal = self.direction.cross(other.direction);
+        let offset = (other.origin - self.origin).wide();
+        if normal == [0; 3] || dot(offset, normal) != 0 {
+            return None;
+        }
+
+        let denominator = dot(normal, normal);
+        let t = Rational::new(dot(cross(offset, other.direction.wide()), normal), denominator);
+        let u = Rational::new(dot(cross(offset, self.direction.wide()), normal), denominator);
+        let point = [
+            Rational::from(self.origin.x) + &t * &Rational::from(self.direction.x),
+            Rational::from(self.origin.y) + &t * &Rational::from(self.direction.y),
+            Rational::from(self.origin.z) + &t * &Rational::from(self.direction.z),
+        ];
+        Some(Crossing { t, u, point })
+    }
+}
+
+/// Segment between two lattice points, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment3 {
+    pub start: Point3,
+    pub end: Point3,
+}
+
+impl Segment3 {
+    pub fn new(start: Point3, end: Point3) -> Segment3 {
+        Segment3 { start, end }
+    }
+
+    /// Ray from the start, reaching the end at `t = 1`.
+    pub fn ray(&self) -> Ray3 {
+        Ray3::new(self.start, self.end - self.start)
+    }
+
+    pub fn bounding_box(&self) -> Aabb3 {
+        Aabb3::new(self.start, self.end)
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        let direction = self.end - self.start;
+        direction.cross(point - self.start) == [0; 3] && self.bounding_box().contains(point)
+    }
+
+    /// Whether both segments share at least one point, overlapping collinear ones included.
+    ///
+    /// ```
+    /// use aoc_core::geometry::{Point3, Segment3};
+    ///
+    /// let brick = Segment3::new(Point3::new(0, 0, 2), Point3::new(0, 3, 2));
+    /// assert!(brick.intersects(&Segment3::new(Point3::new(0, 3, 2), Point3::new(0, 5, 2))));
+    /// assert!(brick.intersects(&Segment3::new(Point3::new(-1, 1, 2), Point3::new(1, 1, 2))));
+    /// assert!(!brick.intersects(&Segment3::new(Point3::new(-1, 1, 3), Point3::new(1, 1, 3))));
+    /// assert_eq!(brick.points().count(), 4);
+    /// ```
+    pub fn intersects(&self, other: &Segment3) -> bool {
+        if let Some(crossing) = self.ray().crossing(&other.ray()) {
+            return crossing.is_within();
+        }
+        // Parallel or skew, or one of them is a single point
+        self.contains(other.start) || self.contains(other.end) || other.contains(self.start) || other.contains(self.end)
+    }
+
+    /// Lattice points of the segment, from start to end.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let direction = self.end - self.start;
+        let steps = gcd(gcd(direction.x.abs(), direction.y.abs()), direction.z.abs());
+        let step = if steps == 0 {
+            direction
+        } else {
+            Point3::new(direction.x / steps, direction.y / steps, direction.z / steps)
+        };
+        let start = self.start;
+        (0..=steps).map(move |i| start + step * i)
+    }
+}
+
+/// Where a point is relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the signed area of a polygon given by its vertices in order, the last one joining back
+/// to the first (shoelace formula). Doubled so that it stays an integer; positive when the
+/// vertices turn counter-clockwise with the y axis pointing up.
+pub fn signed_double_area(vertices: &[Point2]) -> i64 {
+    let area = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, &b)| a.cross(b))
+        .sum::<i128>();
+    arith::convert(area)
+}
+
+/// Number of lattice points on the edges of a polygon.
+pub fn boundary_points(vertices: &[Point2]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    arith::sum(edges.map(|(&a, &b)| gcd((b.x - a.x).abs(), (b.y - a.y).abs())))
+}
+
+/// Number of lattice points strictly inside a polygon whose vertices are lattice points (Pick's
+/// theorem).
+///
+/// ```
+/// use aoc_core::geometry::{boundary_points, interior_points, Point2};
+///
+/// // The lagoon dug by the example of 2023 day 18, part 1
+/// let vertices = [(0, 0), (6, 0), (6, 5), (4, 5), (4, 7), (6, 7), (6, 9), (1, 9), (1, 7), (0, 7), (0, 5), (2, 5), (2, 2), (0, 2)];
+/// let vertices = vertices.map(|(x, y)| Point2::new(x, y));
+/// assert_eq!(interior_points(&vertices) + boundary_points(&vertices), 62);
+/// ```
+pub fn interior_points(vertices: &[Point2]) -> i64 {
+    (signed_double_area(vertices).abs() - boundary_points(vertices)) / 2 + 1
+}
+
+/// Where a point is relative to a polygon given by its vertices in order.
+///
+/// ```
+/// use aoc_core::geometry::{containment, Containment, Point2};
+///
+/// let square = [Point2::new(0, 0), Point2::new(4, 0), Point2::new(4, 4), Point2::new(0, 4)];
+/// assert_eq!(containment(&square, Point2::new(2, 2)), Containment::Inside);
+/// assert_eq!(containment(&square, Point2::new(4, 1)), Containment::Boundary);
+/// assert_eq!(containment(&square, Point2::new(5, 2)), Containment::Outside);
+/// ```
+pub fn containment(vertices: &[Point2], point: Point2) -> Containment {
+    let mut inside = false;
+    for (&a, &b) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+        let side = (b - a).cross(point - a);
+        if side == 0 && (a.x.min(b.x)..=a.x.max(b.x)).contains(&point.x) && (a.y.min(b.y)..=a.y.max(b.y)).contains(&point.y) {
+            return Containment::Boundary;
+        }
+        // Crossings of the half-line from the point towards increasing x
+        if (a.y > point.y) != (b.y > point.y) && (side > 0) == (b.y > a.y) {
+            inside = !inside;
+        }
+    }
+
+    if inside {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray2(origin: (i64, i64), direction: (i64, i64)) -> Ray2 {
+        Ray2::new(Point2::new(origin.0, origin.1), Point2::new(direction.0, direction.1))
+    }
+
+    fn segment3(start: (i64, i64, i64), end: (i64, i64, i64)) -> Segment3 {
+        Segment3::new(Point3::new(start.0, start.1, start.2), Point3::new(end.0, end.1, end.2))
+    }
+
+    #[test]
+    fn parallel_and_collinear_rays_do_not_cross() {
+        let ray = ray2((0, 0), (2, 1));
+        assert_eq!(ray.crossing(&ray2((0, 1), (2, 1))), None);
+        assert_eq!(ray.crossing(&ray2((0, 1), (-4, -2))), None);
+        // Collinear, whether they overlap or not
+        assert_eq!(ray.crossing(&ray2((4, 2), (-2, -1))), None);
+        assert_eq!(ray.crossing(&ray2((-4, -2), (-2, -1))), None);
+
+        let ray = Ray3::new(Point3::new(0, 0, 0), Point3::new(1, 2, 3));
+        assert_eq!(ray.crossing(&Ray3::new(Point3::new(1, 0, 0), Point3::new(2, 4, 6))), None);
+        assert_eq!(ray.crossing(&Ray3::new(Point3::new(2, 4, 6), Point3::new(-1, -2, -3))), None);
+    }
+
+    #[test]
+    fn rays_touching_at_an_end() {
+        // The second ray starts on the first one
+        let crossing = ray2((0, 0), (1, 1)).crossing(&ray2((3, 3), (1, -1))).unwrap();
+        assert_eq!((&crossing.t, &crossing.u), (&Rational::from(3), &Rational::from(0)));
+        assert_eq!(crossing.point, [Rational::from(3), Rational::from(3)]);
+        assert!(crossing.is_ahead());
+
+        // Both start at the same point
+        let crossing = ray2((1, 2), (1, 0)).crossing(&ray2((1, 2), (0, -1))).unwrap();
+        assert_eq!((&crossing.t, &crossing.u), (&Rational::from(0), &Rational::from(0)));
+        assert!(crossing.is_ahead() && crossing.is_within());
+
+        // Just behind the origin of the second ray
+        let crossing = ray2((0, 0), (1, 1)).crossing(&ray2((3, 4), (0, 2))).unwrap();
+        assert_eq!(crossing.u, Rational::new(-1, 2));
+        assert!(!crossing.is_ahead());
+    }
+
+    #[test]
+    fn segments_touching_or_collinear() {
+        let brick = segment3((0, 0, 0), (0, 4, 0));
+        // End to end, and an end on the middle of the other
+        assert!(brick.intersects(&segment3((0, 4, 0), (3, 4, 0))));
+        assert!(brick.intersects(&segment3((-2, 2, 0), (0, 2, 0))));
+        assert!(!brick.intersects(&segment3((-2, 2, 0), (-1, 2, 0))));
+        // Collinear, overlapping, touching or apart
+        assert!(brick.intersects(&segment3((0, 3, 0), (0, 6, 0))));
+        assert!(brick.intersects(&segment3((0, 6, 0), (0, 4, 0))));
+        assert!(!brick.intersects(&segment3((0, 5, 0), (0, 6, 0))));
+        // Parallel, and a single point
+        assert!(!brick.intersects(&segment3((1, 0, 0), (1, 4, 0))));
+        assert!(brick.intersects(&segment3((0, 1, 0), (0, 1, 0))));
+        assert!(!brick.intersects(&segment3((0, 1, 1), (0, 1, 1))));
+    }
+}
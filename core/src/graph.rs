@@ -0,0 +1,489 @@
+//! Graphs whose nodes are named by labels, as puzzles describe them.
+//!
+//! Labels are interned to dense indices, so that the algorithms work on plain vectors:
+//!
+//! ```
+//! use aoc_core::graph::Graph;
+//!
+//! let mut graph = Graph::directed();
+//! for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")] {
+//!     let (from, to) = (graph.node(from), graph.node(to));
+//!     graph.add_edge(from, to);
+//! }
+//!
+//! assert_eq!(graph.bfs(graph.index("a").unwrap())[graph.index("d").unwrap()], Some(3));
+//! assert_eq!(graph.strongly_connected_components().len(), 2);
+//! assert_eq!(graph.topological_order(), None);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use crate::random::Rng;
+
+/// Two-way mapping between labels and dense indices, in order of first appearance.
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    indices: HashMap<String, usize>,
+    labels: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Index of a label, allocating the next one if the label is new.
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&index) = self.indices.get(label) {
+            return index;
+        }
+        self.labels.push(label.to_string());
+        self.indices.insert(label.to_string(), self.labels.len() - 1);
+        self.labels.len() - 1
+    }
+
+    pub fn get(&self, label: &str) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &str {
+        &self.labels[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+/// Graph stored as adjacency lists, keeping the order edges were added in. Undirected graphs
+/// store each edge in both directions.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    directed: bool,
+    labels: Interner,
+    adjacency: Vec<Vec<usize>>,
+}
+
+/// Result of [`Graph::min_cut`]: the number of edges cut, and the nodes on one side of the cut.
+#[derive(Debug, Clone)]
+pub struct Cut {
+    pub size: usize,
+    pub side: Vec<usize>,
+}
+
+impl Graph {
+    pub fn directed() -> Graph {
+        Graph {
+            directed: true,
+            labels: Interner::new(),
+            adjacency: Vec::new(),
+        }
+    }
+
+    pub fn undirected() -> Graph {
+        Graph {
+            directed: false,
+            ..Graph::directed()
+        }
+    }
+
+    /// Index of the node with this label, adding the node if it does not exist yet.
+    pub fn node(&mut self, label: &str) -> usize {
+        let index = self.labels.intern(label);
+        if index == self.adjacency.len() {
+            self.adjacency.push(Vec::new());
+        }
+        index
+    }
+
+    /// Index of an existing node.
+    pub fn index(&self, label: &str) -> Option<usize> {
+        self.labels.get(label)
+    }
+
+    pub fn label(&self, node: usize) -> &str {
+        self.labels.label(node)
+    }
+
+    /// Adds an edge, which an undirected graph lists from both ends, a loop once.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.adjacency[from].push(to);
+        if !self.directed && from != to {
+            self.adjacency[to].push(from);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// Nodes an edge leads to from `node`, in the order the edges were added.
+    pub fn neighbours(&self, node: usize) -> &[usize] {
+        &self.adjacency[node]
+    }
+
+    /// Every edge once, as `(from, to)`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency.iter().enumerate().flat_map(move |(from, neighbours)| {
+            neighbours
+                .iter()
+                .filter(move |&&to| self.directed || from <= to)
+                .map(move |&to| (from, to))
+        })
+    }
+
+    /// Number of edges from `start` to every node, `None` for the unreachable ones.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].unwrap();
+            for &neighbour in &self.adjacency[node] {
+                if distances[neighbour].is_none() {
+                    distances[neighbour] = Some(distance + 1);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Nodes reachable from `start`, in depth-first preorder.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            stack.extend(self.adjacency[node].iter().rev().filter(|&&neighbour| !visited[neighbour]));
+        }
+
+        order
+    }
+
+    /// Strongly connected components (Kosaraju), in topological order of the condensed graph.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // Finishing order of a depth-first search, without recursion
+        let mut visited = vec![false; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, edge)) = stack.last_mut() {
+                match self.adjacency[*node].get(*edge) {
+                    Some(&neighbour) => {
+                        *edge += 1;
+                        if !visited[neighbour] {
+                            visited[neighbour] = true;
+                            stack.push((neighbour, 0));
+                        }
+                    }
+                    None => {
+                        finished.push(*node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let mut transposed = vec![Vec::new(); self.len()];
+        for (from, to) in self.edges() {
+            transposed[to].push(from);
+            if !self.directed {
+                transposed[from].push(to);
+            }
+        }
+
+        let mut assigned = vec![false; self.len()];
+        let mut components = Vec::new();
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &neighbour in &transposed[node] {
+                    if !assigned[neighbour] {
+                        assigned[neighbour] = true;
+                        component.push(neighbour);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Order in which every edge goes forward (Kahn), or `None` if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degrees = vec![0; self.len()];
+        for (_, to) in self.edges() {
+            in_degrees[to] += 1;
+        }
+
+        let mut queue = (0..self.len()).filter(|&node| in_degrees[node] == 0).collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &neighbour in &self.adjacency[node] {
+                in_degrees[neighbour] -= 1;
+                if in_degrees[neighbour] == 0 {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Smallest cut of an undirected graph found by `trials` runs of Karger's random edge
+    /// contraction, stopping early on a cut of at most `target` edges.
+    ///
+    /// ```
+    /// use aoc_core::graph::Graph;
+    /// use aoc_core::random::Rng;
+    ///
+    /// // Two triangles joined by a single edge
+    /// let mut graph = Graph::undirected();
+    /// for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("x", "y"), ("y", "z"), ("z", "x"), ("c", "x")] {
+    ///     let (from, to) = (graph.node(from), graph.node(to));
+    ///     graph.add_edge(from, to);
+    /// }
+    ///
+    /// let cut = graph.min_cut(&mut Rng::new(1), 100, 1);
+    /// assert_eq!((cut.size, cut.side.len()), (1, 3));
+    /// ```
+    pub fn min_cut(&self, rng: &mut Rng, trials: usize, target: usize) -> Cut {
+        assert!(!self.directed, "cuts are computed on undirected graphs");
+        let edges = self.edges().filter(|(from, to)| from != to).collect::<Vec<_>>();
+
+        let mut best: Option<Cut> = None;
+        for _ in 0..trials {
+            let mut contraction = Contraction::new(self.len());
+            let mut remaining = edges.clone();
+            while contraction.count() > 2 && !remaining.is_empty() {
+                let (from, to) = remaining.swap_remove(rng.below(remaining.len() as u64) as usize);
+                contraction.contract(from, to);
+            }
+
+            let size = edges
+                .iter()
+                .filter(|&&(from, to)| contraction.group(from) != contraction.group(to))
+                .count();
+            if best.as_ref().is_none_or(|best| size < best.size) {
+                let group = contraction.group(0);
+                let side = (0..self.len()).filter(|&node| contraction.group(node) == group).collect();
+                best = Some(Cut { size, side });
+            }
+            if best.as_ref().is_some_and(|best| best.size <= target) {
+                break;
+            }
+        }
+
+        best.expect("at least one trial")
+    }
+
+    /// Graphviz description of the graph, labelling nodes with their puzzle labels.
+    pub fn to_dot(&self, name: &str) -> String {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+
+        let mut dot = format!("{} {} {{\n", kind, dot_string(name));
+        for node in 0..self.len() {
+            writeln!(dot, "    {} [label={}];", node, dot_string(self.label(node))).unwrap();
+        }
+        for (from, to) in self.edges() {
+            writeln!(dot, "    {} {} {};", from, arrow, to).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Quoted DOT string of a text, in which only quotes and backslashes are escaped.
+fn dot_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Nodes merged into groups by contracting edges, as a union-find.
+#[derive(Debug, Clone)]
+pub struct Contraction {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl Contraction {
+    /// Every node in its own group.
+    pub fn new(len: usize) -> Contraction {
+        Contraction {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// Representative node of the group of `node`.
+    pub fn group(&mut self, node: usize) -> usize {
+        let mut root = node;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut node = node;
+        while self.parents[node] != root {
+            let next = self.parents[node];
+            self.parents[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    /// Merges the groups of both ends of an edge, returning false if they already were one.
+    pub fn contract(&mut self, first: usize, second: usize) -> bool {
+        let (mut first, mut second) = (self.group(first), self.group(second));
+        if first == second {
+            return false;
+        }
+        if self.sizes[first] < self.sizes[second] {
+            (first, second) = (second, first);
+        }
+        self.parents[second] = first;
+        self.sizes[first] += self.sizes[second];
+        self.count -= 1;
+        true
+    }
+
+    /// Number of nodes in the group of `node`.
+    pub fn size(&mut self, node: usize) -> usize {
+        let group = self.group(node);
+        self.sizes[group]
+    }
+
+    /// Number of groups left.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(mut graph: Graph, edges: &[(&str, &str)]) -> Graph {
+        for (from, to) in edges {
+            let (from, to) = (graph.node(from), graph.node(to));
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn group_compresses_the_whole_path() {
+        let mut contraction = Contraction {
+            parents: vec![0, 0, 1, 2, 3],
+            sizes: vec![5, 1, 1, 1, 1],
+            count: 1,
+        };
+        assert_eq!(contraction.group(4), 0);
+        assert_eq!(contraction.parents, [0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn contract_counts_groups_and_sizes() {
+        let mut contraction = Contraction::new(4);
+        assert!(contraction.contract(0, 1));
+        assert!(contraction.contract(2, 1));
+        assert!(!contraction.contract(0, 2));
+        assert_eq!((contraction.count(), contraction.size(2), contraction.size(3)), (2, 3, 1));
+    }
+
+    #[test]
+    fn components_of_a_cyclic_graph() {
+        let graph = graph(Graph::directed(), &[("a", "b"), ("b", "a"), ("b", "c"), ("c", "d"), ("d", "c")]);
+        let mut components = graph.strongly_connected_components();
+        components.iter_mut().for_each(|component| component.sort());
+        assert_eq!(components, [vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn components_of_an_acyclic_graph() {
+        let graph = graph(Graph::directed(), &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.len(), 4);
+        assert_eq!(components.first(), Some(&vec![0]));
+        assert_eq!(components.last(), Some(&vec![3]));
+    }
+
+    #[test]
+    fn topological_order() {
+        let acyclic = graph(Graph::directed(), &[("c", "a"), ("a", "b"), ("c", "b")]);
+        assert_eq!(acyclic.topological_order(), Some(vec![0, 1, 2]));
+
+        let cyclic = graph(Graph::directed(), &[("x", "a"), ("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_eq!(cyclic.topological_order(), None);
+    }
+
+    #[test]
+    fn min_cut_of_two_squares_joined_by_two_edges() {
+        let edges = [
+            ("a", "b"), ("b", "c"), ("c", "d"), ("d", "a"), ("a", "c"), ("b", "d"),
+            ("w", "x"), ("x", "y"), ("y", "z"), ("z", "w"), ("w", "y"), ("x", "z"),
+            ("a", "w"), ("c", "y"),
+        ];
+        let graph = graph(Graph::undirected(), &edges);
+        let cut = graph.min_cut(&mut Rng::new(7), 200, 2);
+        let mut side = cut.side.iter().map(|&node| graph.label(node)).collect::<Vec<_>>();
+        side.sort();
+        assert_eq!(cut.size, 2);
+        assert!(side == ["a", "b", "c", "d"] || side == ["w", "x", "y", "z"]);
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let graph = graph(Graph::directed(), &[("say \"hi\"", "back\\slash"), ("back\\slash", "café\tbar")]);
+        assert_eq!(
+            graph.to_dot("quotes"),
+            "digraph \"quotes\" {\n    0 [label=\"say \\\"hi\\\"\"];\n    1 [label=\"back\\\\slash\"];\n    2 [label=\"café\tbar\"];\n    0 -> 1;\n    1 -> 2;\n}\n"
+        );
+    }
+
+    #[test]
+    fn undirected_loops_once() {
+        let graph = graph(Graph::undirected(), &[("a", "a"), ("a", "b")]);
+        assert_eq!(graph.neighbours(0), [0, 1]);
+        assert_eq!(graph.edges().collect::<Vec<_>>(), [(0, 0), (0, 1)]);
+        assert_eq!(graph.to_dot("loop"), "graph \"loop\" {\n    0 [label=\"a\"];\n    1 [label=\"b\"];\n    0 -- 0;\n    0 -- 1;\n}\n");
+    }
+}
@@ -0,0 +1,118 @@
+//! Ways of getting the puzzle input to a solution: fully loaded (from stdin or a memory-mapped
+//! file), or streamed record by record for inputs too large to hold in memory.
+
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{read_to_string, stdin, BufRead, Write};
+use std::str::from_utf8;
+
+use crate::random::Rng;
+use crate::Options;
+
+/// The whole puzzle input, either read from stdin or mapped from a file.
+pub enum Input {
+    Loaded(String),
+    Mapped(Mmap),
+}
+
+impl Input {
+    /// Maps the file at `path` if there is one, reads stdin otherwise.
+    pub fn load(path: Option<&str>) -> Input {
+        match path {
+            Some(path) => {
+                let file = File::open(path).unwrap();
+                // The file is only read, and is expected not to change while the solution runs.
+                let map = unsafe { Mmap::map(&file) }.unwrap();
+                Input::Mapped(map)
+            }
+            None => Input::Loaded(read_to_string(stdin()).unwrap()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Input::Loaded(input) => input,
+            Input::Mapped(map) => from_utf8(map).unwrap(),
+        }
+    }
+}
+
+/// Streaming mode of a solution, for the days whose records can be processed one at a time.
+pub struct Streaming {
+    /// Solves both parts in a single pass over the input.
+    pub solve: fn(&mut dyn BufRead, &Options) -> [String; 2],
+    /// Writes a random, valid input of roughly the given size in bytes, for throughput benchmarks.
+    pub generate: fn(&mut Rng, u64, &mut dyn Write),
+}
+
+/// Calls `f` on every record of the input, records being separated by `separator`.
+///
+/// Only one record is held in memory at a time; a trailing line break is not part of a record.
+pub fn for_each_record(reader: &mut dyn BufRead, separator: u8, mut f: impl FnMut(&[u8])) {
+    let mut buffer = Vec::new();
+
+    loop {
+        buffer.clear();
+        if reader.read_until(separator, &mut buffer).unwrap() == 0 {
+            break;
+        }
+
+        while matches!(buffer.last(), Some(&c) if c == separator || c == b'\n' || c == b'\r') {
+            buffer.pop();
+        }
+        f(&buffer);
+    }
+}
+
+/// Calls `f` on every line of the input, one line in memory at a time.
+pub fn for_each_line(reader: &mut dyn BufRead, mut f: impl FnMut(&str)) {
+    for_each_record(reader, b'\n', |line| f(from_utf8(line).unwrap()));
+}
+
+/// Calls `f` on every whitespace separated token of the input, one token in memory at a time.
+pub fn for_each_token(reader: &mut dyn BufRead, mut f: impl FnMut(&str)) {
+    let mut token = Vec::new();
+
+    loop {
+        let buffer = reader.fill_buf().unwrap();
+        if buffer.is_empty() {
+            break;
+        }
+
+        let length = buffer.len();
+        for &c in buffer {
+            if c.is_ascii_whitespace() {
+                if !token.is_empty() {
+                    f(from_utf8(&token).unwrap());
+                    token.clear();
+                }
+            } else {
+                token.push(c);
+            }
+        }
+        reader.consume(length);
+    }
+
+    if !token.is_empty() {
+        f(from_utf8(&token).unwrap());
+    }
+}
+
+/// Writes lines produced by `line` until at least `size` bytes have been written.
+pub fn generate_lines(
+    rng: &mut Rng,
+    size: u64,
+    out: &mut dyn Write,
+    mut line: impl FnMut(&mut Rng, &mut Vec<u8>),
+) {
+    let mut written = 0;
+    let mut buffer = Vec::new();
+
+    while written < size {
+        buffer.clear();
+        line(rng, &mut buffer);
+        buffer.push(b'\n');
+        out.write_all(&buffer).unwrap();
+        written += buffer.len() as u64;
+    }
+}
@@ -0,0 +1,486 @@
+//! Pieces shared by every puzzle of every event year: how a solution is described, how it is
+//! run, and the helpers that kept being rewritten from one day to the next.
+
+pub mod arith;
+pub mod bitgrid;
+pub mod geometry;
+pub mod graph;
+pub mod input;
+pub mod math;
+pub mod numeric;
+pub mod parse;
+pub mod random;
+mod registry;
+
+use std::env;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{stdin, BufRead, BufReader};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use arith::Overflow;
+use input::{Input, Streaming};
+use parse::ParseError;
+pub use registry::Registry;
+
+/// A puzzle solution, identified by its event year and day.
+///
+/// Solutions are declared with the [`solution!`] macro, which also records which parts are
+/// implemented.
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub parts: [Part; 2],
+    /// Single pass, bounded memory mode, for the days that support it.
+    pub streaming: Option<Streaming>,
+    /// Other implementations of the parts, run side by side with them by [`compare`].
+    pub variants: &'static [Variant],
+}
+
+/// One part of a puzzle: either a function solving it from the raw input, or nothing yet.
+pub enum Part {
+    Implemented(fn(&str, &Options) -> Result<String, Failure>),
+    NotImplemented,
+}
+
+/// Another named implementation of a part, such as a first edition kept next to a faster one.
+pub struct Variant {
+    pub name: &'static str,
+    /// Index of the part it solves, 0 for part 1.
+    pub part: usize,
+    pub solve: fn(&str, &Options) -> Result<String, Failure>,
+}
+
+/// What a part function returns.
+pub trait Answer {
+    /// The answer as printed, or why the part has none.
+    fn into_answer(self) -> Result<String, Failure>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String, Failure> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, String, numeric::BigInt, numeric::Rational);
+
+impl<T: Answer, E: Into<Failure>> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Failure> {
+        self.map_err(Into::into).and_then(Answer::into_answer)
+    }
+}
+
+/// A `--param` setting whose value the day does not understand.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParamError {
+    pub name: &'static str,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl ParamError {
+    pub fn new(name: &'static str, message: impl Display) -> ParamError {
+        ParamError {
+            name,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parameter `{}`: {}", self.name, self.message)
+    }
+}
+
+impl Error for ParamError {}
+
+/// Why a part has no answer: its input, or one of the settings it was given.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Failure {
+    Input(ParseError),
+    Param(ParamError),
+}
+
+impl Failure {
+    /// What failed, as printed in place of the answer.
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Failure::Input(_) => "invalid input",
+            Failure::Param(_) => "invalid parameter",
+        }
+    }
+
+    /// Prints the details of the failure of a part on stderr, returning what a streaming mode
+    /// prints in place of its answer.
+    pub fn report(&self, part: usize) -> String {
+        eprintln!("part {}: {}", part, self);
+        format!("part {}: {}", part, self.summary())
+    }
+}
+
+impl From<ParseError> for Failure {
+    fn from(error: ParseError) -> Failure {
+        Failure::Input(error)
+    }
+}
+
+impl From<ParamError> for Failure {
+    fn from(error: ParamError) -> Failure {
+        Failure::Param(error)
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Input(error) => error.fmt(f),
+            Failure::Param(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for Failure {}
+
+/// How much of a puzzle is implemented.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Status {
+    Implemented,
+    Partial,
+    NotImplemented,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Padded, so that listings can align the columns after it
+        f.pad(match self {
+            Status::Implemented => "implemented",
+            Status::Partial => "partial",
+            Status::NotImplemented => "not implemented",
+        })
+    }
+}
+
+impl Part {
+    pub fn is_implemented(&self) -> bool {
+        matches!(self, Part::Implemented(_))
+    }
+}
+
+impl Solution {
+    pub fn status(&self) -> Status {
+        match (self.parts[0].is_implemented(), self.parts[1].is_implemented()) {
+            (true, true) => Status::Implemented,
+            (false, false) => Status::NotImplemented,
+            _ => Status::Partial,
+        }
+    }
+}
+
+/// Declares the `SOLUTION` of a day from its parse and part functions.
+///
+/// The parse function takes the raw input, the part functions take the parsed input and the
+/// [`Options`], and return an [`Answer`]: a number or a string, or a `Result` of one when the
+/// input or a setting may turn out invalid for that part only, its error being a [`ParseError`], a
+/// [`ParamError`] or either as a [`Failure`]. A parse function returning a
+/// `Result<_, ParseError>` is given as `try_parse` instead of `parse`. Parts that are left out are
+/// registered as not implemented. A day supporting the streaming mode also gives its single pass solver and its
+/// input generator (see [`Streaming`]), and other implementations of a part are listed by name as
+/// `variants` (see [`Variant`]):
+///
+/// ```ignore
+/// aoc_core::solution! {
+///     year: 2023,
+///     day: 1,
+///     parse: parse_input,
+///     part_1: solve_part_1,
+///     part_2: solve_part_2,
+///     stream: stream_input,
+///     generate: generate_input,
+///     variants: [
+///         part_1 "regex": solve_part_1_regex,
+///     ],
+/// }
+/// ```
+#[macro_export]
+macro_rules! solution {
+    (year: $year:literal, day: $day:literal $(,)?) => {
+        pub const SOLUTION: $crate::Solution = $crate::Solution {
+            year: $year,
+            day: $day,
+            parts: [$crate::Part::NotImplemented, $crate::Part::NotImplemented],
+            streaming: None,
+            variants: &[],
+        };
+    };
+    (
+        year: $year:literal,
+        day: $day:literal,
+        $parse_kind:ident: $parse:path,
+        part_1: $part_1:path
+        $(, part_2: $part_2:path)?
+        $(, stream: $stream:path, generate: $generate:path)?
+        $(, variants: [$($variant_part:ident $variant_name:literal: $variant:path),* $(,)?])?
+        $(,)?
+    ) => {
+        pub const SOLUTION: $crate::Solution = $crate::Solution {
+            year: $year,
+            day: $day,
+            parts: [
+                $crate::solution!(@part $parse_kind $parse, $part_1),
+                $crate::solution!(@part $parse_kind $parse $(, $part_2)?),
+            ],
+            streaming: $crate::solution!(@streaming $($stream, $generate)?),
+            variants: &[$($(
+                $crate::Variant {
+                    name: $variant_name,
+                    part: $crate::solution!(@index $variant_part),
+                    solve: $crate::solution!(@solve $parse_kind $parse, $variant),
+                },
+            )*)?],
+        };
+    };
+    (@part $parse_kind:ident $parse:path) => {
+        $crate::Part::NotImplemented
+    };
+    (@part $parse_kind:ident $parse:path, $part:path) => {
+        $crate::Part::Implemented($crate::solution!(@solve $parse_kind $parse, $part))
+    };
+    (@solve $parse_kind:ident $parse:path, $part:path) => {
+        |input, options| {
+            let parsed = $crate::solution!(@parse $parse_kind $parse, input).map_err($crate::Failure::Input)?;
+            $crate::Answer::into_answer($part(&parsed, options))
+        }
+    };
+    (@index part_1) => {
+        0
+    };
+    (@index part_2) => {
+        1
+    };
+    (@parse parse $parse:path, $input:expr) => {
+        Ok::<_, $crate::parse::ParseError>($parse($input))
+    };
+    (@parse try_parse $parse:path, $input:expr) => {
+        $parse($input)
+    };
+    (@streaming) => {
+        None
+    };
+    (@streaming $stream:path, $generate:path) => {
+        Some($crate::input::Streaming {
+            solve: $stream,
+            generate: $generate,
+        })
+    };
+}
+
+/// Command line options understood by every solution.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Print how each answer was derived, before the answer itself.
+    pub explain: bool,
+    /// Process the input record by record, for the days supporting it.
+    pub stream: bool,
+    /// Read the input from this file (memory-mapped) instead of stdin.
+    pub input: Option<String>,
+    /// Settings specific to a day, given as `--param name=value`, or `--param name` for `true`.
+    pub params: Vec<(String, String)>,
+}
+
+impl Options {
+    /// Builds the options from the command line arguments, returning the arguments that are not
+    /// options.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> (Options, Vec<String>) {
+        let mut options = Options::default();
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--explain" => options.explain = true,
+                "--stream" => options.stream = true,
+                "--input" => options.input = args.next(),
+                "--param" => {
+                    if let Some(param) = args.next() {
+                        let (name, value) = param.split_once('=').unwrap_or((&param, "true"));
+                        options.params.push((name.to_string(), value.to_string()));
+                    }
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        (options, positional)
+    }
+
+    /// Value of a day specific setting, the last one given if it was given several times.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().rev().find(|(param, _)| param == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// Entry point of a single day binary: reads the puzzle input and runs the solution.
+pub fn main(solution: &Solution) {
+    let (options, _) = Options::from_args(env::args().skip(1));
+
+    execute(solution, &options);
+}
+
+/// Gets the input the way the options ask for, then runs the solution on it.
+pub fn execute(solution: &Solution, options: &Options) {
+    arith::install_overflow_hook();
+
+    if options.stream {
+        match &solution.streaming {
+            Some(streaming) => {
+                let mut reader: Box<dyn BufRead> = match &options.input {
+                    Some(path) => Box::new(BufReader::new(File::open(path).unwrap())),
+                    None => Box::new(stdin().lock()),
+                };
+                run_streaming(solution, streaming, &mut reader, options);
+                return;
+            }
+            None => eprintln!(
+                "{} day {} has no streaming mode, loading the whole input",
+                solution.year, solution.day
+            ),
+        }
+    }
+
+    let input = Input::load(options.input.as_deref());
+    run(solution, input.as_str(), options);
+}
+
+/// Runs the streaming mode of a solution, printing both answers once the input is consumed.
+pub fn run_streaming(solution: &Solution, streaming: &Streaming, reader: &mut dyn BufRead, options: &Options) {
+    let start_time = Instant::now();
+
+    match catch_overflow(|| (streaming.solve)(reader, options)) {
+        Ok(answers) => {
+            for answer in answers {
+                println!("{}", answer);
+            }
+        }
+        Err(overflow) => {
+            println!("overflow");
+            eprintln!("{} day {} streaming: {}", solution.year, solution.day, overflow);
+        }
+    }
+    eprintln!("{} µs", (Instant::now() - start_time).as_micros());
+}
+
+/// Runs `f`, turning an [`Overflow`] raised by the checked arithmetic into an error.
+fn catch_overflow<T>(f: impl FnOnce() -> T) -> Result<T, Overflow> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| match payload.downcast::<Overflow>() {
+        Ok(overflow) => *overflow,
+        Err(payload) => resume_unwind(payload),
+    })
+}
+
+/// Runs both parts of a solution on an input, one answer per line, reporting the elapsed time on
+/// stderr. Parts that are not implemented are reported as such instead of being silently skipped.
+pub fn run(solution: &Solution, input: &str, options: &Options) {
+    for (part_index, part) in solution.parts.iter().enumerate() {
+        match part {
+            Part::Implemented(solve) => {
+                let start_time = Instant::now();
+                match catch_overflow(|| solve(input, options)) {
+                    Ok(Ok(answer)) => println!("{}", answer),
+                    Ok(Err(failure)) => report_failure(solution, part_index, failure.summary(), &failure),
+                    Err(overflow) => report_failure(solution, part_index, "overflow", &overflow),
+                }
+                eprintln!("part {}: {} µs", part_index + 1, (Instant::now() - start_time).as_micros());
+            }
+            Part::NotImplemented => {
+                println!("part {}: not implemented", part_index + 1);
+            }
+        }
+    }
+}
+
+/// Runs every implementation of each part `runs` times on an input, and prints their answers and
+/// best times side by side. Returns the parts whose implementations disagree.
+pub fn compare(solution: &Solution, input: &str, options: &Options, runs: usize) -> Vec<usize> {
+    arith::install_overflow_hook();
+
+    let mut disagreeing = Vec::new();
+    println!("part  variant               answer                time (µs)  relative");
+    for (part_index, part) in solution.parts.iter().enumerate() {
+        let Part::Implemented(solve) = part else {
+            continue;
+        };
+        let mut implementations = vec![("default", *solve)];
+        for variant in solution.variants.iter().filter(|variant| variant.part == part_index) {
+            implementations.push((variant.name, variant.solve));
+        }
+
+        let mut results = Vec::new();
+        for (name, solve) in implementations {
+            let mut best_time = Duration::MAX;
+            let mut answer = String::new();
+            for _ in 0..runs.max(1) {
+                let start_time = Instant::now();
+                answer = match catch_overflow(|| solve(input, options)) {
+                    Ok(Ok(answer)) => answer,
+                    Ok(Err(failure)) => failure.summary().to_string(),
+                    Err(_) => "overflow".to_string(),
+                };
+                best_time = best_time.min(start_time.elapsed());
+            }
+            results.push((name, answer, best_time));
+        }
+
+        let reference = results[0].2.as_secs_f64();
+        for (name, answer, time) in &results {
+            println!(
+                "{:<4}  {:<20}  {:<20}  {:>9}  {:>8.2}",
+                part_index + 1,
+                name,
+                answer,
+                time.as_micros(),
+                time.as_secs_f64() / reference
+            );
+        }
+        if results.iter().any(|(_, answer, _)| *answer != results[0].1) {
+            disagreeing.push(part_index);
+        }
+    }
+
+    disagreeing
+}
+
+/// Prints a short failure in place of the answer of a part, and its details on stderr.
+fn report_failure(solution: &Solution, part_index: usize, failure: &str, details: &dyn Display) {
+    println!("part {}: {}", part_index + 1, failure);
+    eprintln!("{} day {} part {}: {}", solution.year, solution.day, part_index + 1, details);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_of_parts() {
+        let invalid: Result<u32, ParseError> = Err(ParseError::missing("digits").at_line(3));
+        let failure = invalid.into_answer().unwrap_err();
+        assert_eq!((failure.summary(), failure.to_string()), ("invalid input", "line 3: missing digits".to_string()));
+
+        let unknown: Result<u32, ParamError> = Err(ParamError::new("size", "expected a number, found \"big\""));
+        let failure = unknown.into_answer().unwrap_err();
+        assert_eq!(failure.summary(), "invalid parameter");
+        assert_eq!(failure.to_string(), "parameter `size`: expected a number, found \"big\"");
+        assert_eq!(failure.report(2), "part 2: invalid parameter");
+
+        assert_eq!(Ok::<_, Failure>(Ok::<_, ParseError>(42)).into_answer(), Ok("42".to_string()));
+    }
+}
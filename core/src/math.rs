@@ -0,0 +1,15 @@
+use crate::arith::{self, Number};
+
+/// Greatest common divisor of two non-negative numbers.
+pub fn gcd<T: Number>(mut first: T, mut second: T) -> T {
+    while second != T::zero() {
+        (first, second) = (second.clone(), first % second);
+    }
+    first
+}
+
+/// Least common multiple of two numbers. Divides before multiplying so that only a result which
+/// does not fit overflows.
+pub fn lcm<T: Number>(first: T, second: T) -> T {
+    arith::mul(first.clone() / gcd(first, second.clone()), second)
+}
@@ -0,0 +1,635 @@
+//! Arbitrary precision integers and exact rationals, for the values that do not fit in 128 bits
+//! or that floats would round.
+//!
+//! ```
+//! use aoc_core::numeric::{BigInt, Rational};
+//!
+//! let big = BigInt::from(u128::MAX) * BigInt::from(u128::MAX);
+//! assert_eq!(big.to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+//! assert_eq!((&big / &BigInt::from(u128::MAX)), BigInt::from(u128::MAX));
+//! assert_eq!(u64::try_from(&big).ok(), None);
+//!
+//! let third = Rational::new(1, 3);
+//! assert_eq!(&third + &Rational::new(1, 6), Rational::new(1, 2));
+//! assert_eq!((Rational::new(-7, 2).floor(), Rational::new(-7, 2).ceil()), (BigInt::from(-4), BigInt::from(-3)));
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::arith::Number;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+/// Integer of any size, as a sign and a little endian magnitude of 32 bits limbs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// No trailing zero limb, so that zero is the empty magnitude and is never negative.
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn one() -> BigInt {
+        BigInt::from(1)
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: Vec<u32>) -> BigInt {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_magnitude(false, self.magnitude.clone())
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        let mut result = BigInt::one();
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Quotient rounded towards negative infinity, where `/` rounds towards zero like the
+    /// primitive integers.
+    pub fn div_floor(&self, divisor: &BigInt) -> BigInt {
+        let (quotient, remainder) = self.div_rem(divisor);
+        if !remainder.is_zero() && remainder.negative != divisor.negative {
+            quotient - BigInt::one()
+        } else {
+            quotient
+        }
+    }
+
+    /// Quotient rounded towards zero, and remainder of the sign of `self`.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "division by zero");
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+        (
+            BigInt::from_magnitude(self.negative != divisor.negative, quotient),
+            BigInt::from_magnitude(self.negative, remainder),
+        )
+    }
+
+    /// Largest integer whose square is at most `self`, which must not be negative.
+    pub fn isqrt(&self) -> BigInt {
+        assert!(!self.negative, "square root of a negative number");
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+
+        // Newton's iteration from a power of two above the root, decreasing until it settles
+        let bits = 32 * self.magnitude.len() as u32 - self.magnitude.last().unwrap().leading_zeros();
+        let mut root = BigInt::one().shl(bits.div_ceil(2));
+        loop {
+            let next = (&root + &(self / &root)).shr1();
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = &a % &b;
+            (a, b) = (b, remainder);
+        }
+        a
+    }
+
+    fn shl(&self, bits: u32) -> BigInt {
+        let mut magnitude = vec![0; (bits / 32) as usize];
+        let shift = bits % 32;
+        let mut carry = 0;
+        for &limb in &self.magnitude {
+            magnitude.push((limb << shift) | carry);
+            carry = if shift == 0 { 0 } else { limb >> (32 - shift) };
+        }
+        magnitude.push(carry);
+        BigInt::from_magnitude(self.negative, magnitude)
+    }
+
+    fn shr1(&self) -> BigInt {
+        let mut magnitude = self.magnitude.clone();
+        let mut carry = 0;
+        for limb in magnitude.iter_mut().rev() {
+            let next_carry = *limb << 31;
+            *limb = (*limb >> 1) | carry;
+            carry = next_carry;
+        }
+        BigInt::from_magnitude(self.negative, magnitude)
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0;
+    for (i, &limb) in long.iter().enumerate() {
+        let total = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+/// `a - b`, where `a` is at least `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &limb) in a.iter().enumerate() {
+        let total = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        difference.push(total.rem_euclid(1 << 32) as u32);
+        borrow = (total < 0) as i64;
+    }
+    difference
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0_u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, &y) in b.iter().enumerate() {
+            let total = product[i + j] as u64 + x as u64 * y as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+/// Long division, one limb at a time for a single limb divisor, one bit at a time otherwise.
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_magnitude(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+
+    if b.len() == 1 {
+        let divisor = b[0] as u64;
+        let mut quotient = vec![0; a.len()];
+        let mut remainder = 0_u64;
+        for (i, &limb) in a.iter().enumerate().rev() {
+            let current = (remainder << 32) | limb as u64;
+            quotient[i] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        return (quotient, vec![remainder as u32]);
+    }
+
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = BigInt::zero();
+    let divisor = BigInt::from_magnitude(false, b.to_vec());
+    for bit in (0..32 * a.len()).rev() {
+        remainder = remainder.shl(1);
+        if a[bit / 32] >> (bit % 32) & 1 == 1 {
+            remainder = &remainder + &BigInt::one();
+        }
+        if remainder >= divisor {
+            remainder = &remainder - &divisor;
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder.magnitude)
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_magnitude(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_magnitude(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_magnitude(self.negative, add_magnitude(&self.magnitude, &other.magnitude));
+        }
+        match compare_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_magnitude(other.negative, sub_magnitude(&other.magnitude, &self.magnitude)),
+            _ => BigInt::from_magnitude(self.negative, sub_magnitude(&self.magnitude, &other.magnitude)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_magnitude(self.negative != other.negative, mul_magnitude(&self.magnitude, &other.magnitude))
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+/// Operators on owned values, forwarding to the ones on references.
+macro_rules! forward_owned {
+    ($t:ty: $($trait:ident $method:ident),*) => {
+        $(
+            impl $trait for $t {
+                type Output = $t;
+
+                fn $method(self, other: $t) -> $t {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned!(BigInt: Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+/// Never overflows, so the checked operations always succeed.
+impl Number for BigInt {
+    fn zero() -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one() -> BigInt {
+        BigInt::one()
+    }
+
+    fn checked_add(self, other: BigInt) -> Option<BigInt> {
+        Some(self + other)
+    }
+
+    fn checked_sub(self, other: BigInt) -> Option<BigInt> {
+        Some(self - other)
+    }
+
+    fn checked_mul(self, other: BigInt) -> Option<BigInt> {
+        Some(self * other)
+    }
+}
+
+macro_rules! impl_primitive_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                #[allow(unused_comparisons)]
+                fn from(value: $t) -> BigInt {
+                    let negative = value < 0;
+                    let magnitude = if negative { (value as i128).unsigned_abs() } else { value as u128 };
+                    let limbs = (0..4).map(|i| (magnitude >> (32 * i)) as u32).collect();
+                    BigInt::from_magnitude(negative, limbs)
+                }
+            }
+
+            impl TryFrom<&BigInt> for $t {
+                type Error = BigInt;
+
+                fn try_from(value: &BigInt) -> Result<$t, BigInt> {
+                    if value.magnitude.len() > 4 {
+                        return Err(value.clone());
+                    }
+                    let magnitude = value.magnitude.iter().rev().fold(0_u128, |total, &limb| (total << 32) | limb as u128);
+                    let converted = if value.negative {
+                        0_i128.checked_sub_unsigned(magnitude).and_then(|value| <$t>::try_from(value).ok())
+                    } else {
+                        <$t>::try_from(magnitude).ok()
+                    };
+                    converted.ok_or_else(|| value.clone())
+                }
+            }
+
+            impl TryFrom<BigInt> for $t {
+                type Error = BigInt;
+
+                fn try_from(value: BigInt) -> Result<$t, BigInt> {
+                    <$t>::try_from(&value)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_conversions!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    /// Decimal digits, with an optional sign.
+    fn from_str(text: &str) -> Result<BigInt, String> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(format!("invalid integer `{}`", text));
+        }
+
+        let ten = BigInt::from(10);
+        let mut value = BigInt::zero();
+        for chunk in digits.as_bytes().chunks(9) {
+            let chunk_value = std::str::from_utf8(chunk).unwrap().parse::<u32>().unwrap();
+            value = &(&value * &ten.pow(chunk.len() as u32)) + &BigInt::from(chunk_value);
+        }
+        Ok(BigInt::from_magnitude(negative, value.magnitude))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // Groups of 9 digits, least significant first
+        let mut groups = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_magnitude(&magnitude, &[1_000_000_000]);
+            groups.push(remainder.first().copied().unwrap_or(0));
+            magnitude = BigInt::from_magnitude(false, quotient).magnitude;
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", groups.pop().unwrap())?;
+        for group in groups.iter().rev() {
+            write!(f, "{:09}", group)?;
+        }
+        Ok(())
+    }
+}
+
+/// Exact fraction, kept reduced with a positive denominator so that equal values compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    pub fn new(numerator: impl Into<BigInt>, denominator: impl Into<BigInt>) -> Rational {
+        let (numerator, denominator) = (numerator.into(), denominator.into());
+        assert!(!denominator.is_zero(), "zero denominator");
+
+        let gcd = numerator.gcd(&denominator);
+        let sign = if denominator.is_negative() { -BigInt::one() } else { BigInt::one() };
+        Rational {
+            numerator: &(&numerator / &gcd) * &sign,
+            denominator: &(&denominator / &gcd) * &sign,
+        }
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::one()
+    }
+
+    pub fn floor(&self) -> BigInt {
+        self.numerator.div_floor(&self.denominator)
+    }
+
+    pub fn ceil(&self) -> BigInt {
+        -(-&self.numerator).div_floor(&self.denominator)
+    }
+}
+
+impl<T: Into<BigInt>> From<T> for Rational {
+    fn from(value: T) -> Rational {
+        Rational {
+            numerator: value.into(),
+            denominator: BigInt::one(),
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -&self.numerator,
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, other: &Rational) -> Rational {
+        Rational::new(
+            &(&self.numerator * &other.denominator) + &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, other: &Rational) -> Rational {
+        self + &-other
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, other: &Rational) -> Rational {
+        Rational::new(&self.numerator * &other.numerator, &self.denominator * &other.denominator)
+    }
+}
+
+impl Div for &Rational {
+    type Output = Rational;
+
+    fn div(self, other: &Rational) -> Rational {
+        Rational::new(&self.numerator * &other.denominator, &self.denominator * &other.numerator)
+    }
+}
+
+forward_owned!(Rational: Add add, Sub sub, Mul mul, Div div);
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    /// Value of one to four random limbs, of either sign.
+    fn random_i128(rng: &mut Rng) -> i128 {
+        let limbs = 1 + rng.below(4);
+        let value = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - 32 * limbs);
+        let value = value.min(i128::MAX as u128) as i128;
+        if rng.below(2) == 0 { value } else { -value }
+    }
+
+    #[test]
+    fn division_matches_the_primitive_integers() {
+        let mut rng = Rng::new(23);
+        for _ in 0..2000 {
+            let (a, b) = (random_i128(&mut rng), random_i128(&mut rng));
+            if b == 0 {
+                continue;
+            }
+            let (quotient, remainder) = BigInt::from(a).div_rem(&BigInt::from(b));
+            assert_eq!((quotient, remainder), (BigInt::from(a / b), BigInt::from(a % b)), "{} / {}", a, b);
+
+            let floor = if a % b != 0 && (a % b < 0) != (b < 0) { a / b - 1 } else { a / b };
+            assert_eq!(BigInt::from(a).div_floor(&BigInt::from(b)), BigInt::from(floor), "{} / {}", a, b);
+        }
+    }
+
+    #[test]
+    fn division_by_multi_limb_divisors() {
+        let a: BigInt = "-340282366920938463463374607431768211457123456789".parse().unwrap();
+        for b in ["4294967296", "-18446744073709551617", "340282366920938463463374607431768211455"] {
+            let b: BigInt = b.parse().unwrap();
+            let (quotient, remainder) = a.div_rem(&b);
+            assert_eq!(&(&quotient * &b) + &remainder, a);
+            assert!(remainder.is_negative() && remainder.abs() < b.abs(), "{} % {}", a, b);
+        }
+        let huge = BigInt::from(u128::MAX).pow(3);
+        assert_eq!(&huge / &BigInt::from(u128::MAX).pow(2), BigInt::from(u128::MAX));
+        assert_eq!((&huge % &BigInt::from(u128::MAX)), BigInt::zero());
+    }
+
+    #[test]
+    fn division_of_zero() {
+        let (quotient, remainder) = BigInt::zero().div_rem(&BigInt::from(-5));
+        assert_eq!((quotient.is_negative(), remainder.is_negative()), (false, false));
+        assert_eq!((quotient, remainder), (BigInt::zero(), BigInt::zero()));
+        assert_eq!(BigInt::zero().div_floor(&BigInt::from(u128::MAX)), BigInt::zero());
+        assert_eq!(BigInt::from(-3).div_rem(&BigInt::from(5)), (BigInt::zero(), BigInt::from(-3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let _ = &BigInt::one() / &BigInt::zero();
+    }
+
+    #[test]
+    fn rationals_are_normalised() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!((Rational::new(6, -4).numerator(), Rational::new(6, -4).denominator()), (&BigInt::from(-3), &BigInt::from(2)));
+        assert_eq!(Rational::new(0, -7), Rational::from(0));
+        assert_eq!(Rational::new(0, -7).denominator(), &BigInt::one());
+        assert_eq!(Rational::new(-10, -5), Rational::from(2));
+
+        let big = BigInt::from(u128::MAX);
+        assert_eq!(Rational::new(&big * &BigInt::from(3), &big * &BigInt::from(-6)).to_string(), "-1/2");
+        assert_eq!(&Rational::new(1, 3) - &Rational::new(1, 3), Rational::from(0));
+        assert!((&Rational::new(2, 3) / &Rational::new(-4, 9)).denominator() > &BigInt::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "zero denominator")]
+    fn zero_denominator() {
+        Rational::new(1, 0);
+    }
+}
@@ -0,0 +1,384 @@
+//! Declarative parsing of puzzle inputs.
+//!
+//! A line format is written as a [`Pattern`] template, literal text with `{}` captures in
+//! between, and its captures are converted to a tuple of [`Capture`] types. Lists and blocks are
+//! split with [`separated`] and [`blocks`], and [`lines`] tags errors with their line number:
+//!
+//! ```
+//! use aoc_core::parse::{lines, Pattern};
+//!
+//! const NODE: Pattern = Pattern::new("{} = ({}, {})");
+//!
+//! let nodes: Vec<(&str, &str, &str)> = lines("AAA = (BBB, CCC)\nBBB = (DDD, EEE)", |line| NODE.parse(line)).unwrap();
+//! assert_eq!(nodes[1], ("BBB", "DDD", "EEE"));
+//!
+//! let error = lines("AAA = (BBB, CCC)\nBBB = DDD", |line| NODE.parse::<(&str, &str, &str)>(line)).unwrap_err();
+//! assert_eq!(error.to_string(), "line 2: expected ` = (` in `BBB = DDD`");
+//! ```
+
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Why an input could not be parsed, and on which line when known.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// A literal part of a pattern is missing.
+    Expected { literal: &'static str, found: String },
+    /// A capture is not a valid value of its type.
+    Invalid { expected: &'static str, found: String },
+    /// The input ended before this was read.
+    Missing { expected: &'static str },
+}
+
+impl ParseError {
+    pub fn expected(literal: &'static str, found: &str) -> ParseError {
+        ParseError::new(ErrorKind::Expected {
+            literal,
+            found: found.to_string(),
+        })
+    }
+
+    pub fn invalid(expected: &'static str, found: &str) -> ParseError {
+        ParseError::new(ErrorKind::Invalid {
+            expected,
+            found: found.to_string(),
+        })
+    }
+
+    pub fn missing(expected: &'static str) -> ParseError {
+        ParseError::new(ErrorKind::Missing { expected })
+    }
+
+    fn new(kind: ErrorKind) -> ParseError {
+        ParseError { line: None, kind }
+    }
+
+    /// Sets the line the error occurred on, unless a more precise one is already known.
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        match &self.kind {
+            ErrorKind::Expected { literal, found } => write!(f, "expected `{}` in `{}`", literal, found),
+            ErrorKind::Invalid { expected, found } => write!(f, "expected {}, found `{}`", expected, found),
+            ErrorKind::Missing { expected } => write!(f, "missing {}", expected),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line format: literal text with `{}` captures in between, e.g. `"Game {}: {}"`.
+///
+/// A capture extends up to the first occurrence of the text following it, except the last one
+/// which extends up to the text ending the line. Two captures must be separated by some text.
+///
+/// ```
+/// use aoc_core::parse::{Hex, Pattern};
+///
+/// const INSTRUCTION: Pattern = Pattern::new("{} {} (#{})");
+///
+/// let (direction, distance, Hex(color)): (char, isize, Hex<usize>) = INSTRUCTION.parse("R 6 (#70c710)").unwrap();
+/// assert_eq!((direction, distance, color), ('R', 6, 0x70c710));
+/// assert!(INSTRUCTION.parse::<(char, isize, Hex<usize>)>("R six (#70c710)").is_err());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Pattern {
+    template: &'static str,
+}
+
+impl Pattern {
+    pub const fn new(template: &'static str) -> Pattern {
+        Pattern { template }
+    }
+
+    /// Matches a line, returning the text of each capture.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Vec<&'a str>, ParseError> {
+        let mut literals = self.template.split("{}").peekable();
+        let prefix = literals.next().unwrap();
+        let mut rest = line.strip_prefix(prefix).ok_or_else(|| ParseError::expected(prefix, line))?;
+
+        let mut captures = Vec::new();
+        while let Some(literal) = literals.next() {
+            if literals.peek().is_none() {
+                let capture = rest.strip_suffix(literal).ok_or_else(|| ParseError::expected(literal, rest))?;
+                captures.push(capture);
+                rest = "";
+            } else {
+                assert!(!literal.is_empty(), "captures of {:?} are not separated", self.template);
+                let end = rest.find(literal).ok_or_else(|| ParseError::expected(literal, rest))?;
+                captures.push(&rest[..end]);
+                rest = &rest[end + literal.len()..];
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(ParseError::expected("end of line", rest));
+        }
+
+        Ok(captures)
+    }
+
+    /// Matches a line and converts its captures, in order.
+    pub fn parse<'a, T: FromCaptures<'a>>(&self, line: &'a str) -> Result<T, ParseError> {
+        let captures = self.captures(line)?;
+        assert_eq!(captures.len(), T::COUNT, "{:?} does not have {} captures", self.template, T::COUNT);
+        T::from_captures(&captures)
+    }
+}
+
+/// A value that can be read from the text of a capture.
+///
+/// Numbers allow surrounding spaces, so that aligned columns such as `Card   1` parse as well.
+/// A `Vec` of captures reads a whitespace separated list.
+pub trait Capture<'a>: Sized {
+    fn capture(text: &'a str) -> Result<Self, ParseError>;
+}
+
+impl<'a> Capture<'a> for &'a str {
+    fn capture(text: &'a str) -> Result<Self, ParseError> {
+        Ok(text)
+    }
+}
+
+impl Capture<'_> for String {
+    fn capture(text: &str) -> Result<Self, ParseError> {
+        Ok(text.to_string())
+    }
+}
+
+impl Capture<'_> for char {
+    fn capture(text: &str) -> Result<Self, ParseError> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseError::invalid("a single character", text)),
+        }
+    }
+}
+
+impl<'a, T: Capture<'a>> Capture<'a> for Vec<T> {
+    fn capture(text: &'a str) -> Result<Self, ParseError> {
+        text.split_ascii_whitespace().map(T::capture).collect()
+    }
+}
+
+macro_rules! impl_capture_number {
+    ($($t:ty),*) => {
+        $(
+            impl Capture<'_> for $t {
+                fn capture(text: &str) -> Result<Self, ParseError> {
+                    text.trim().parse().map_err(|_| ParseError::invalid(type_name::<$t>(), text))
+                }
+            }
+
+            impl Capture<'_> for Hex<$t> {
+                fn capture(text: &str) -> Result<Self, ParseError> {
+                    <$t>::from_str_radix(text.trim(), 16)
+                        .map(Hex)
+                        .map_err(|_| ParseError::invalid(concat!("hexadecimal ", stringify!($t)), text))
+                }
+            }
+        )*
+    };
+}
+
+/// A number written in hexadecimal, without prefix.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Hex<T>(pub T);
+
+impl_capture_number!(i32, i64, isize, u8, u32, u64, usize);
+
+impl Capture<'_> for f64 {
+    fn capture(text: &str) -> Result<Self, ParseError> {
+        text.trim().parse().map_err(|_| ParseError::invalid("f64", text))
+    }
+}
+
+/// The captures of a [`Pattern`], converted to a tuple.
+pub trait FromCaptures<'a>: Sized {
+    const COUNT: usize;
+
+    fn from_captures(captures: &[&'a str]) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_captures {
+    ($count:literal: $($t:ident $index:tt),+) => {
+        impl<'a, $($t: Capture<'a>),+> FromCaptures<'a> for ($($t,)+) {
+            const COUNT: usize = $count;
+
+            fn from_captures(captures: &[&'a str]) -> Result<Self, ParseError> {
+                Ok(($($t::capture(captures[$index])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1: A 0);
+impl_from_captures!(2: A 0, B 1);
+impl_from_captures!(3: A 0, B 1, C 2);
+impl_from_captures!(4: A 0, B 1, C 2, D 3);
+impl_from_captures!(5: A 0, B 1, C 2, D 3, E 4);
+
+/// Parses each item of a `separator` separated list.
+///
+/// ```
+/// use aoc_core::parse::{separated, Capture, Pattern};
+///
+/// let reveal: Vec<(usize, &str)> = separated("3 blue, 4 red", ", ", |cubes| Pattern::new("{} {}").parse(cubes)).unwrap();
+/// assert_eq!(reveal, [(3, "blue"), (4, "red")]);
+///
+/// let numbers: Vec<u32> = separated("1,2,x", ",", u32::capture).unwrap_or_default();
+/// assert!(numbers.is_empty());
+/// ```
+pub fn separated<'a, T>(
+    text: &'a str,
+    separator: &str,
+    parse_item: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.split(separator).map(parse_item).collect()
+}
+
+/// Part of an input separated from the others by blank lines.
+#[derive(Debug, Clone, Copy)]
+pub struct Block<'a> {
+    pub text: &'a str,
+    /// Line number of the first line of the block in the whole input, starting at 1.
+    pub first_line: usize,
+}
+
+impl<'a> Block<'a> {
+    /// Parses each line of the block, tagging errors with their line number in the whole input.
+    pub fn lines<T>(&self, mut parse_line: impl FnMut(&'a str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+        self.text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_line(line).map_err(|error| error.at_line(self.first_line + index)))
+            .collect()
+    }
+
+    /// Parses the block as a whole, tagging errors with its first line number.
+    pub fn parse<T>(&self, parse_block: impl FnOnce(&'a str) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        parse_block(self.text).map_err(|error| error.at_line(self.first_line))
+    }
+}
+
+/// Splits an input into its blocks of lines separated by blank lines, lines of nothing but
+/// whitespace counting as blank so that `\r\n` line endings split the same way.
+///
+/// ```
+/// use aoc_core::parse::blocks;
+///
+/// let blocks = blocks("LR\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\n").collect::<Vec<_>>();
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!((blocks[1].first_line, blocks[1].text.lines().count()), (3, 2));
+/// ```
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let (mut offset, mut line_number) = (0, 1);
+    std::iter::from_fn(move || {
+        // Offset and number of the first line of the block, and end of its last line
+        let mut first = None;
+        let mut end = offset;
+        for line in input[offset..].split_inclusive('\n') {
+            if line.trim_end().is_empty() {
+                if first.is_some() {
+                    break;
+                }
+            } else {
+                first.get_or_insert((offset, line_number));
+                end = offset + line.trim_end_matches(['\r', '\n']).len();
+            }
+            offset += line.len();
+            line_number += 1;
+        }
+        first.map(|(start, first_line)| Block {
+            text: &input[start..end],
+            first_line,
+        })
+    })
+}
+
+/// Parses each line of an input, tagging errors with their line number.
+pub fn lines<'a, T>(input: &'a str, parse_line: impl FnMut(&'a str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    Block { text: input, first_line: 1 }.lines(parse_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: Pattern = Pattern::new("Game {}: {}");
+
+    #[test]
+    fn mismatched_literal() {
+        assert_eq!(GAME.captures("Card 1: 3 blue"), Err(ParseError::expected("Game ", "Card 1: 3 blue")));
+        assert_eq!(GAME.captures("Game 1; 3 blue"), Err(ParseError::expected(": ", "1; 3 blue")));
+    }
+
+    #[test]
+    fn trailing_text() {
+        const PAIR: Pattern = Pattern::new("({}, {})");
+        assert_eq!(PAIR.captures("(1, 2)"), Ok(vec!["1", "2"]));
+        // The last capture extends up to the end of the line, its conversion rejecting the rest
+        assert_eq!(PAIR.captures("(1, 2) (3, 4)"), Ok(vec!["1", "2) (3, 4"]));
+        assert_eq!(PAIR.parse::<(u32, u32)>("(1, 2) (3, 4)"), Err(ParseError::invalid("u32", "2) (3, 4")));
+
+        const PREFIX: Pattern = Pattern::new("seeds:");
+        assert_eq!(PREFIX.captures("seeds: 79"), Err(ParseError::expected("end of line", " 79")));
+    }
+
+    #[test]
+    fn invalid_captures() {
+        assert_eq!(GAME.parse::<(u32, &str)>("Game   12: 3 blue"), Ok((12, "3 blue")));
+        assert_eq!(GAME.parse::<(u32, &str)>("Game x: 3 blue"), Err(ParseError::invalid("u32", "x")));
+        assert_eq!(char::capture("ab"), Err(ParseError::invalid("a single character", "ab")));
+        assert_eq!(Vec::<u8>::capture(" 1 2  300"), Err(ParseError::invalid("u8", "300")));
+    }
+
+    #[test]
+    fn hexadecimal() {
+        assert_eq!(Hex::<u32>::capture("70c710"), Ok(Hex(0x70c710)));
+        assert_eq!(Hex::<u32>::capture("#70c710"), Err(ParseError::invalid("hexadecimal u32", "#70c710")));
+    }
+
+    #[test]
+    fn line_numbers_of_lines() {
+        let error = lines("1\n2\nx\n4", u32::capture).unwrap_err();
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.to_string(), "line 3: expected u32, found `x`");
+    }
+
+    #[test]
+    fn line_numbers_of_blocks() {
+        let input = "\n\nseeds\n\n\na\nb\n\nc\nx\n\n";
+        let blocks = blocks(input).collect::<Vec<_>>();
+        let found = blocks.iter().map(|block| (block.text, block.first_line)).collect::<Vec<_>>();
+        assert_eq!(found, [("seeds", 3), ("a\nb", 6), ("c\nx", 9)]);
+
+        let error = blocks[2].lines(|line| if line == "x" { Err(ParseError::missing("x")) } else { Ok(()) });
+        assert_eq!(error.unwrap_err().line, Some(10));
+        assert_eq!(blocks[1].parse(|_| Err::<(), _>(ParseError::missing("b"))).unwrap_err().line, Some(6));
+    }
+
+    #[test]
+    fn blocks_of_crlf_lines() {
+        let input = "LR\r\n\r\nAAA = (BBB, CCC)\r\nBBB = (DDD, EEE)\r\n \r\nZZZ\r\n";
+        let found = blocks(input).map(|block| (block.text, block.first_line)).collect::<Vec<_>>();
+        assert_eq!(found, [("LR", 1), ("AAA = (BBB, CCC)\r\nBBB = (DDD, EEE)", 3), ("ZZZ", 6)]);
+        assert_eq!(blocks("\r\n\r\n").count(), 0);
+    }
+}
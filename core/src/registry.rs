@@ -0,0 +1,33 @@
+use crate::Solution;
+
+/// All the solutions known to a runner, looked up by (year, day).
+pub struct Registry {
+    solutions: Vec<&'static Solution>,
+}
+
+impl Registry {
+    pub fn new(solutions: &[&'static Solution]) -> Registry {
+        let mut solutions = solutions.to_vec();
+        solutions.sort_by_key(|solution| (solution.year, solution.day));
+
+        for pair in solutions.windows(2) {
+            if (pair[0].year, pair[0].day) == (pair[1].year, pair[1].day) {
+                panic!("{} day {} is registered twice", pair[0].year, pair[0].day);
+            }
+        }
+
+        Registry { solutions }
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&'static Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.year == year && solution.day == day)
+            .copied()
+    }
+
+    /// Solutions ordered by year, then day.
+    pub fn solutions(&self) -> &[&'static Solution] {
+        &self.solutions
+    }
+}
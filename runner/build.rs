@@ -0,0 +1,29 @@
+//! Lists the `SOLUTION` of every day the runner depends on, so that adding a day only takes adding
+//! its crate to the dependencies.
+
+use std::env;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let manifest = read_to_string("Cargo.toml").expect("cannot read the manifest of the runner");
+
+    let mut solutions = String::from("const SOLUTIONS: &[&Solution] = &[\n");
+    for line in manifest.lines() {
+        let Some((name, _)) = line.split_once('=') else {
+            continue;
+        };
+        // Crates of days are named `aoc-<year>-day-<N>`
+        let parts = name.trim().split('-').collect::<Vec<_>>();
+        if let ["aoc", year, "day", day] = parts.as_slice() {
+            if year.parse::<u16>().is_ok() && day.parse::<u8>().is_ok() {
+                solutions.push_str(&format!("    &aoc_{}_day_{}::SOLUTION,\n", year, day));
+            }
+        }
+    }
+    solutions.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    write(Path::new(&out_dir).join("solutions.rs"), solutions).expect("cannot write the list of solutions");
+}
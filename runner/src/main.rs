@@ -0,0 +1,174 @@
+use aoc_core::input::{Input, Streaming};
+use aoc_core::random::Rng;
+use aoc_core::{Options, Registry, Solution};
+use std::env;
+use std::fs::{read_to_string, File};
+use std::io::{stdout, BufReader, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::Instant;
+
+// `SOLUTIONS`, a `SOLUTION` for each `aoc-<year>-day-<N>` dependency of the runner
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+const USAGE: &str = "usage: aoc <year> <day> [--explain] [--stream] [--input <file>] [--param <name>[=<value>]] [< input.txt]
+       aoc list
+       aoc compare <year> <day> [<runs>] [--input <file>] [< input.txt]
+       aoc generate <year> <day> <size> > input.txt
+       aoc bench-stream <year> <day> <size> <file>";
+
+fn main() -> ExitCode {
+    let registry = Registry::new(SOLUTIONS);
+
+    let (options, args) = Options::from_args(env::args().skip(1));
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["list"] => {
+            list(&registry);
+            Ok(())
+        }
+        ["generate", year, day, size] => {
+            find_streaming(&registry, year, day).and_then(|streaming| generate(streaming, size))
+        }
+        ["bench-stream", year, day, size, path] => find_streaming(&registry, year, day)
+            .and_then(|streaming| bench_stream(streaming, size, path)),
+        ["compare", year, day] => find_solution(&registry, year, day).and_then(|solution| compare(solution, &options, "1")),
+        ["compare", year, day, runs] => {
+            find_solution(&registry, year, day).and_then(|solution| compare(solution, &options, runs))
+        }
+        [year, day] => find_solution(&registry, year, day).map(|solution| {
+            aoc_core::execute(solution, &options);
+        }),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn find_solution(registry: &Registry, year: &str, day: &str) -> Result<&'static Solution, String> {
+    let (Ok(year), Ok(day)) = (year.parse(), day.parse()) else {
+        return Err(USAGE.to_string());
+    };
+
+    registry
+        .get(year, day)
+        .ok_or_else(|| format!("no solution registered for {} day {}", year, day))
+}
+
+fn find_streaming(registry: &Registry, year: &str, day: &str) -> Result<&'static Streaming, String> {
+    let solution = find_solution(registry, year, day)?;
+
+    solution
+        .streaming
+        .as_ref()
+        .ok_or_else(|| format!("{} day {} has no streaming mode", solution.year, solution.day))
+}
+
+/// Parses a size in bytes, with an optional `K`, `M` or `G` suffix.
+fn parse_size(size: &str) -> Result<u64, String> {
+    let (digits, unit) = match size.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&size[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&size[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&size[..i], 1 << 30),
+        _ => (size, 1),
+    };
+
+    digits
+        .parse::<u64>()
+        .map(|value| value * unit)
+        .map_err(|_| format!("invalid size: {}", size))
+}
+
+/// Prints which days and parts are implemented, year by year.
+fn list(registry: &Registry) {
+    println!("year  day  part 1  part 2  status           streaming");
+    for solution in registry.solutions() {
+        let [part_1, part_2] = &solution.parts;
+        println!(
+            "{:<4}  {:>3}  {:<6}  {:<6}  {:<15}  {}",
+            solution.year,
+            solution.day,
+            if part_1.is_implemented() { "yes" } else { "no" },
+            if part_2.is_implemented() { "yes" } else { "no" },
+            solution.status(),
+            if solution.streaming.is_some() { "yes" } else { "no" }
+        );
+    }
+}
+
+/// Runs every implementation of the parts of a day, failing if they do not agree.
+fn compare(solution: &Solution, options: &Options, runs: &str) -> Result<(), String> {
+    let runs = runs.parse().map_err(|_| format!("invalid number of runs: {}", runs))?;
+
+    let input = Input::load(options.input.as_deref());
+    let disagreeing = aoc_core::compare(solution, input.as_str(), options, runs);
+
+    match disagreeing.as_slice() {
+        [] => Ok(()),
+        parts => Err(format!(
+            "{} day {}: implementations of part {} disagree",
+            solution.year,
+            solution.day,
+            parts.iter().map(|part| (part + 1).to_string()).collect::<Vec<_>>().join(" and ")
+        )),
+    }
+}
+
+/// Writes a generated input of the given size on stdout.
+fn generate(streaming: &Streaming, size: &str) -> Result<(), String> {
+    let size = parse_size(size)?;
+
+    let mut out = BufWriter::new(stdout().lock());
+    (streaming.generate)(&mut Rng::new(2023), size, &mut out);
+    out.flush().unwrap();
+
+    Ok(())
+}
+
+/// Measures the streaming throughput of a day on a generated input, written to `path` first if
+/// the file does not exist yet.
+fn bench_stream(streaming: &Streaming, size: &str, path: &str) -> Result<(), String> {
+    let size = parse_size(size)?;
+
+    if File::open(path).is_err() {
+        let start_time = Instant::now();
+        let mut out = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+        (streaming.generate)(&mut Rng::new(2023), size, &mut out);
+        out.flush().unwrap();
+        eprintln!("generated {} in {:.2} s", path, start_time.elapsed().as_secs_f64());
+    }
+
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let bytes = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut reader = BufReader::with_capacity(1 << 20, file);
+
+    let start_time = Instant::now();
+    let answers = (streaming.solve)(&mut reader, &Options::default());
+    let elapsed = start_time.elapsed().as_secs_f64();
+
+    println!("answers: {} / {}", answers[0], answers[1]);
+    println!(
+        "{:.1} MiB in {:.2} s => {:.1} MiB/s, peak memory {}",
+        bytes as f64 / (1 << 20) as f64,
+        elapsed,
+        bytes as f64 / (1 << 20) as f64 / elapsed,
+        peak_memory().unwrap_or_else(|| "unknown".to_string())
+    );
+
+    Ok(())
+}
+
+/// Peak resident memory of the process, as reported by Linux.
+fn peak_memory() -> Option<String> {
+    let status = read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .map(|value| value.trim().to_string())
+}